@@ -1,102 +1,29 @@
-use stilo::println_styles;
+use stilo::highlight::{Highlighter, Rust, Theme};
+
+const SOURCE: &str = r#"
+use std::ops::Add;
+
+#[repr(transparent)]
+struct Wrapper<T> {
+    value: T,
+}
+
+impl Add<f32> for Wrapper<f32> {
+    type Output = Wrapper<f32>;
+
+    fn add(self, rhs: f32) -> Self {
+        todo!("not yet implemented :(")
+    }
+}
+
+fn frobnicate<'a>(wrapper: &'a mut Wrapper<f32>) -> &'a f32 {
+    wrapper.value += 1.0;
+    unsafe {
+        &*(wrapper as *const _)
+    }
+}
+"#;
 
 fn main() {
-    println_styles!(
-        "\n";
-        "use ": Red;
-        "std": Yellow;
-        "::": Magenta;
-        "ops": Yellow;
-        "::": Magenta;
-        "Add": Yellow;
-        "\n\n";
-        "#[repr(transparent)]": Blue;
-        "\n";
-        "struct ": Magenta;
-        "Wrapper": Yellow;
-        "<": Cyan;
-        "T": Yellow;
-        "> ": Cyan;
-        "{{": Yellow;
-        "\n    value";
-        ": ": Magenta;
-        "T": Yellow;
-        ",": Cyan;
-        "\n}}": Yellow;
-        "\n\n";
-        "impl ": Red;
-        "Add": Yellow;
-        "<": Cyan;
-        "f32": Yellow;
-        "> for ": Cyan;
-        "Wrapper": Yellow;
-        "<": Cyan;
-        "f32": Yellow;
-        "> ": Cyan;
-        "{{": Yellow;
-        "\n    type ": Magenta;
-        "Output ": Yellow;
-        "= ": Magenta;
-        "Wrapper": Yellow;
-        "<": Cyan;
-        "f32": Yellow;
-        ">;": Cyan;
-        "\n\n    fn ": Red;
-        "add": Blue;
-        "(": Magenta;
-        "self": Red;
-        ", ": Cyan;
-        "rhs";
-        ": ": Magenta;
-        "f32": Yellow;
-        ") -> ": Magenta;
-        "Self ": Red;
-        "}}": Magenta;
-        "\n        ";
-        "todo!(": Blue;
-        "\"": Cyan;
-        "not yet implemented :(": Green;
-        "\"": Cyan;
-        ")": Blue;
-        "\n    }}": Magenta;
-        "\n}}": Yellow;
-        "\n\nfn ": Red;
-        "frobnicate": Blue;
-        "<'": Cyan;
-        "a": Yellow;
-        ">": Cyan;
-        "(": Yellow;
-        "wrapper";
-        ": &": Magenta;
-        "'": Cyan;
-        "a ": Yellow;
-        "mut ": Magenta;
-        "Wrapper": Yellow;
-        "<": Cyan;
-        "f32": Yellow;
-        ">": Cyan;
-        ") ": Yellow;
-        " -> &": Magenta;
-        "'": Cyan;
-        "a f32 {{": Yellow;
-        "\n    wrapper";
-        ".": Magenta;
-        "value ";
-        "+= ": Magenta;
-        "1": Red;
-        ".": Cyan;
-        "0": Red;
-        ";": Cyan;
-        "\n    unsafe ": Red;
-        "{{": Magenta;
-        "\n        &*": Magenta;
-        "(": Blue;
-        "wrapper ";
-        "as ": Red;
-        "*const ": Magenta;
-        "_";
-        ")": Blue;
-        "\n    }}": Magenta;
-        "\n}}": Yellow;
-    );
+    println!("{}", Rust.highlight(SOURCE, &Theme::default()));
 }
@@ -0,0 +1,412 @@
+//! Procedural macros for `stilo`
+//!
+//! Use the re-exports from `stilo` instead of this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use syn::LitStr;
+
+/// Format text with inline style tags, checked at compile time.
+///
+/// See `stilo::markup!` for documentation.
+#[proc_macro]
+pub fn markup(input: TokenStream) -> TokenStream {
+    match expand_markup(input.into()) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Split arguments at each `;`, and call a macro with each argument in brackets
+///
+/// Input is `[path::to::macro] [prefix tokens] arguments`.
+/// Expands to `path::to::macro!(prefix tokens [argument] [argument] ...)`.
+/// Empty arguments are skipped.
+///
+/// Used by `stilo::stylize_many!`, so arguments are not split one token at a time
+#[doc(hidden)]
+#[proc_macro]
+pub fn split_segments(input: TokenStream) -> TokenStream {
+    match expand_split_segments(input.into()) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Split arguments at each `;`, and call the macro with each argument in brackets
+fn expand_split_segments(input: TokenStream2) -> syn::Result<TokenStream2> {
+    let mut tokens = input.into_iter();
+
+    let mut group = || match tokens.next() {
+        Some(TokenTree::Group(group)) => Ok(group.stream()),
+        _ => Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "expected a macro path and prefix in brackets",
+        )),
+    };
+    let path = group()?;
+    let prefix = group()?;
+
+    let mut segments = Vec::new();
+    let mut segment = TokenStream2::new();
+    for token in tokens {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                segments.push(std::mem::take(&mut segment));
+            }
+            token => segment.extend([token]),
+        }
+    }
+    segments.push(segment);
+    let segments = segments.into_iter().filter(|segment| !segment.is_empty());
+
+    Ok(quote! {
+        #path!( #prefix #( [ #segments ] )* )
+    })
+}
+
+/// Convert markup into a single `format!` call, with escape codes in the format string
+fn expand_markup(input: TokenStream2) -> syn::Result<TokenStream2> {
+    let mut tokens = input.into_iter();
+
+    let Some(first) = tokens.next() else {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "expected a string literal",
+        ));
+    };
+    let literal: LitStr = syn::parse2(TokenStream2::from(first))?;
+    let args: TokenStream2 = tokens.collect();
+
+    if let Some(TokenTree::Punct(punct)) = args.clone().into_iter().next() {
+        if punct.as_char() != ',' {
+            return Err(syn::Error::new(punct.span(), "expected `,`"));
+        }
+    }
+
+    let text = render(&literal.value())
+        .map_err(|error| syn::Error::new(literal.span(), error.to_string()))?;
+    let text = LitStr::new(&text, literal.span());
+
+    Ok(quote! {
+        ::std::format!(#text #args)
+    })
+}
+
+/// Error from parsing markup, with the byte offset in the text
+#[derive(Debug, PartialEq)]
+struct MarkupError {
+    offset: usize,
+    message: String,
+}
+
+impl std::fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} (at offset {})", self.message, self.offset)
+    }
+}
+
+/// Render markup as text with escape codes
+///
+/// Tags are written as `<red+bold>`, and closed with `</>`.
+/// A literal `<` is written as `<<`.
+///
+/// Tags can also be written as `[red+bold]`, and closed with `[/]`, the same as `parse_markup` at
+/// runtime. Literal brackets are written as `[[` and `]]`
+fn render(markup: &str) -> Result<String, MarkupError> {
+    let mut output = String::new();
+    // Open tags, with their offsets, and the merged style of each
+    let mut stack: Vec<(usize, Spec)> = Vec::new();
+    let mut chars = markup.char_indices().peekable();
+
+    while let Some((offset, ch)) = chars.next() {
+        let close = match ch {
+            '<' => '>',
+            '[' => ']',
+            _ => {
+                // Escaped `]]`, or a literal `]`
+                if ch == ']' {
+                    chars.next_if(|(_, ch)| *ch == ']');
+                }
+                output.push(ch);
+                continue;
+            }
+        };
+
+        if chars.next_if(|(_, next)| *next == ch).is_some() {
+            output.push(ch);
+            continue;
+        }
+
+        let mut tag = String::new();
+        loop {
+            match chars.next() {
+                Some((_, ch)) if ch == close => break,
+                Some((_, ch)) => tag.push(ch),
+                None => {
+                    return Err(MarkupError {
+                        offset,
+                        message: format!("unterminated tag, use `{ch}{ch}` for a literal `{ch}`"),
+                    })
+                }
+            }
+        }
+
+        if tag == "/" {
+            if stack.pop().is_none() {
+                return Err(MarkupError {
+                    offset,
+                    message: "closing tag has no matching opening tag".into(),
+                });
+            }
+            output += "\x1b[0m";
+            if let Some((_, outer)) = stack.last() {
+                output += &outer.escape();
+            }
+            continue;
+        }
+
+        let spec = Spec::parse(&tag).map_err(|message| MarkupError { offset, message })?;
+        let merged = match stack.last() {
+            Some((_, outer)) => outer.merge(&spec),
+            None => spec,
+        };
+        output += &merged.escape();
+        stack.push((offset, merged));
+    }
+
+    if let Some((offset, _)) = stack.last() {
+        return Err(MarkupError {
+            offset: *offset,
+            message: "tag is not closed, use `</>`".into(),
+        });
+    }
+
+    Ok(output)
+}
+
+/// Decoration names, short names, and codes, in the same order as `Style::format`
+const DECORATIONS: [(&str, Option<&str>, u8); 7] = [
+    ("bold", Some("b"), 1),
+    ("dim", Some("d"), 2),
+    ("italic", Some("i"), 3),
+    ("underline", Some("u"), 4),
+    ("blink", None, 5),
+    ("reverse", Some("r"), 7),
+    ("strikethrough", Some("s"), 9),
+];
+
+/// Color names and foreground codes
+const COLORS: [(&str, u8); 8] = [
+    ("Black", 30),
+    ("Red", 31),
+    ("Green", 32),
+    ("Yellow", 33),
+    ("Blue", 34),
+    ("Magenta", 35),
+    ("Cyan", 36),
+    ("White", 37),
+];
+
+/// Style of a tag, using the same syntax as `Style` parsing
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Spec {
+    color: Option<Vec<u8>>,
+    background: Option<Vec<u8>>,
+    decorations: [bool; DECORATIONS.len()],
+}
+
+impl Spec {
+    /// Parse a tag, such as `red+bold` or `Red on Blue + i`
+    fn parse(tag: &str) -> Result<Self, String> {
+        let mut spec = Spec::default();
+
+        for (i, name) in tag.split('+').map(str::trim).enumerate() {
+            if name.is_empty() {
+                // Allow leading `+`
+                if i == 0 && tag.contains('+') {
+                    continue;
+                }
+                return Err(format!("empty style in tag `{tag}`"));
+            }
+
+            if let Some(index) = DECORATIONS
+                .iter()
+                .position(|(long, short, _)| name == *long || Some(name) == *short)
+            {
+                spec.decorations[index] = true;
+                continue;
+            }
+
+            let (color, background) = match name.strip_prefix("on ") {
+                Some(background) => ("", Some(background)),
+                None => match name.split_once(" on ") {
+                    Some((color, background)) => (color.trim(), Some(background)),
+                    None => (name, None),
+                },
+            };
+
+            if let Some(background) = background {
+                spec.background = Some(color_params(background.trim(), true)?);
+            }
+            if !color.is_empty() {
+                spec.color = Some(
+                    color_params(color, false)
+                        .map_err(|_| format!("unknown color or decoration `{name}`"))?,
+                );
+            }
+        }
+
+        Ok(spec)
+    }
+
+    /// Merge an inner style over this style
+    fn merge(&self, inner: &Spec) -> Spec {
+        let mut decorations = self.decorations;
+        for (decoration, inner) in decorations.iter_mut().zip(inner.decorations) {
+            *decoration |= inner;
+        }
+
+        Spec {
+            color: inner.color.clone().or_else(|| self.color.clone()),
+            background: inner.background.clone().or_else(|| self.background.clone()),
+            decorations,
+        }
+    }
+
+    /// Get the escape code to apply this style
+    fn escape(&self) -> String {
+        let mut params = Vec::new();
+        params.extend(self.color.iter().flatten());
+        params.extend(self.background.iter().flatten());
+        for (applied, (_, _, code)) in self.decorations.iter().zip(DECORATIONS) {
+            if *applied {
+                params.push(code);
+            }
+        }
+
+        if params.is_empty() {
+            return String::new();
+        }
+
+        let params: Vec<_> = params.iter().map(|param| param.to_string()).collect();
+        format!("\x1b[{}m", params.join(";"))
+    }
+}
+
+/// Get the ANSI parameters for a color name, 256-color palette index, or hex color
+fn color_params(name: &str, background: bool) -> Result<Vec<u8>, String> {
+    let offset = if background { 10 } else { 0 };
+    let error = || format!("unknown color `{name}`");
+
+    if let Some(hex) = name.strip_prefix('#') {
+        if hex.len() != 6 || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(error());
+        }
+        let channel = |i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| error());
+        return Ok(vec![38 + offset, 2, channel(0)?, channel(2)?, channel(4)?]);
+    }
+
+    if name.starts_with(|ch: char| ch.is_ascii_digit()) {
+        let index = name.parse().map_err(|_| error())?;
+        return Ok(vec![38 + offset, 5, index]);
+    }
+
+    COLORS
+        .iter()
+        .find(|(color, _)| color.eq_ignore_ascii_case(name))
+        .map(|(_, code)| vec![code + offset])
+        .ok_or_else(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_segments_works() {
+        let expand = |input: &str| {
+            expand_split_segments(input.parse().unwrap())
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(
+            expand("[m] [@a x] \"a\": Red; b => {c; d} ;; e"),
+            quote!(m!(@a x ["a": Red] [b => {c; d}] [e])).to_string()
+        );
+        assert_eq!(expand("[a::m] []"), quote!(a::m!()).to_string());
+    }
+
+    #[test]
+    fn render_works() {
+        assert_eq!(render("Hello").unwrap(), "Hello");
+        assert_eq!(render("<red>Hello</>").unwrap(), "\x1b[31mHello\x1b[0m");
+        assert_eq!(
+            render("Hello <Red+bold>{name}</>!").unwrap(),
+            "Hello \x1b[31;1m{name}\x1b[0m!"
+        );
+        assert_eq!(
+            render("<+i+b>Hello</> <u>World</>").unwrap(),
+            "\x1b[1;3mHello\x1b[0m \x1b[4mWorld\x1b[0m"
+        );
+        assert_eq!(
+            render("<Red on Blue + r>x</><on 208>y</><#ff8700>z</>").unwrap(),
+            "\x1b[31;44;7mx\x1b[0m\x1b[48;5;208my\x1b[0m\x1b[38;2;255;135;0mz\x1b[0m"
+        );
+        assert_eq!(render("1 << 2 > 0").unwrap(), "1 < 2 > 0");
+    }
+
+    #[test]
+    fn render_works_w_brackets() {
+        assert_eq!(render("[red]Hello[/]").unwrap(), "\x1b[31mHello\x1b[0m");
+        assert_eq!(
+            render("[red]a <b>b</> c[/]").unwrap(),
+            render("<red>a <b>b</> c</>").unwrap()
+        );
+        assert_eq!(render("[[1]] ]]] <<").unwrap(), "[1] ]] <");
+    }
+
+    #[test]
+    fn render_works_w_nesting() {
+        assert_eq!(
+            render("<red>a <b>b <blue>c</> d</> e</>").unwrap(),
+            concat!(
+                "\x1b[31ma ",
+                "\x1b[31;1mb ",
+                "\x1b[34;1mc",
+                "\x1b[0m\x1b[31;1m d",
+                "\x1b[0m\x1b[31m e",
+                "\x1b[0m",
+            )
+        );
+    }
+
+    #[test]
+    fn render_errors() {
+        let error = |markup| render(markup).unwrap_err();
+
+        assert_eq!(
+            error("Hello <purple>x</>"),
+            MarkupError {
+                offset: 6,
+                message: "unknown color or decoration `purple`".into()
+            }
+        );
+        assert_eq!(error("ab <red>x").offset, 3);
+        assert_eq!(error("abc </>").offset, 4);
+        assert_eq!(error("a <red").offset, 2);
+        assert_eq!(error("<red++b>x</>").offset, 0);
+        assert_eq!(error("<>x</>").offset, 0);
+        assert_eq!(error("a []x[/]").offset, 2);
+        assert_eq!(
+            error("a [red").message,
+            "unterminated tag, use `[[` for a literal `[`"
+        );
+        assert_eq!(error("<on pink>x</>").message, "unknown color `pink`");
+        assert_eq!(error("<#+f+f+f>x</>").offset, 0);
+        assert_eq!(color_params("#+f+f+f", false), Err("unknown color `#+f+f+f`".into()));
+        assert_eq!(color_params("#ff8700", true), Ok(vec![48, 2, 255, 135, 0]));
+    }
+}
@@ -0,0 +1,76 @@
+/// Preset of characters for drawing borders
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{Border, Table};
+/// let table = Table::new().border(Border::Double).row(["a", "b"]);
+/// assert_eq!(table.to_string(), "╔═══╦═══╗\n║ a ║ b ║\n╚═══╩═══╝");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Border {
+    /// No border
+    None,
+    /// `+`, `-`, and `|`
+    Ascii,
+    /// `┌─┐`
+    #[default]
+    Single,
+    /// `╔═╗`
+    Double,
+    /// `╭─╮`
+    Rounded,
+    /// `┏━┓`
+    Heavy,
+}
+
+/// Characters of a `Border`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BorderChars {
+    pub horizontal: char,
+    pub vertical: char,
+    pub top_left: char,
+    pub top: char,
+    pub top_right: char,
+    pub left: char,
+    pub cross: char,
+    pub right: char,
+    pub bottom_left: char,
+    pub bottom: char,
+    pub bottom_right: char,
+}
+
+impl BorderChars {
+    /// Create from characters, in the same order as the fields
+    const fn new(chars: [char; 11]) -> Self {
+        let [horizontal, vertical, top_left, top, top_right, left, cross, right, bottom_left, bottom, bottom_right] =
+            chars;
+        Self {
+            horizontal,
+            vertical,
+            top_left,
+            top,
+            top_right,
+            left,
+            cross,
+            right,
+            bottom_left,
+            bottom,
+            bottom_right,
+        }
+    }
+}
+
+impl Border {
+    /// Get the characters of the border, or `None` for no border
+    pub(crate) const fn chars(self) -> Option<BorderChars> {
+        Some(BorderChars::new(match self {
+            Border::None => return None,
+            Border::Ascii => ['-', '|', '+', '+', '+', '+', '+', '+', '+', '+', '+'],
+            Border::Single => ['─', '│', '┌', '┬', '┐', '├', '┼', '┤', '└', '┴', '┘'],
+            Border::Double => ['═', '║', '╔', '╦', '╗', '╠', '╬', '╣', '╚', '╩', '╝'],
+            Border::Rounded => ['─', '│', '╭', '┬', '╮', '├', '┼', '┤', '╰', '┴', '╯'],
+            Border::Heavy => ['━', '┃', '┏', '┳', '┓', '┣', '╋', '┫', '┗', '┻', '┛'],
+        }))
+    }
+}
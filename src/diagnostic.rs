@@ -0,0 +1,733 @@
+use std::{fmt, ops::Range};
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{Color::*, Style, StyledString};
+
+/// Number of spaces for a tab in source code
+const TAB_WIDTH: usize = 4;
+
+/// Severity of a `Diagnostic`, or of a note after it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// `error`
+    Error,
+    /// `warning`
+    Warning,
+    /// `note`
+    Note,
+    /// `help`
+    Help,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+            Severity::Help => "help",
+        })
+    }
+}
+
+/// Label under part of the source code of a `Diagnostic`
+///
+/// The range is of byte offsets in the source code.
+/// Primary labels are underlined with `^`, and secondary labels with `-`.
+/// Messages with newlines are written over many lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    range: Range<usize>,
+    message: String,
+    primary: bool,
+}
+
+impl Label {
+    /// Create a primary label, such as where an error is
+    pub fn primary(range: Range<usize>, message: impl Into<String>) -> Self {
+        Self {
+            range,
+            message: message.into(),
+            primary: true,
+        }
+    }
+
+    /// Create a secondary label, such as for related code
+    pub fn secondary(range: Range<usize>, message: impl Into<String>) -> Self {
+        Self {
+            primary: false,
+            ..Self::primary(range, message)
+        }
+    }
+}
+
+/// Styles for each part of a `Diagnostic`
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{style, Diagnostic, DiagnosticTheme};
+/// let theme = DiagnosticTheme {
+///     gutter: style!(Cyan),
+///     ..Default::default()
+/// };
+///
+/// println!("{}", Diagnostic::warning("unused variable").render(&theme));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiagnosticTheme {
+    /// `error`, and primary labels of errors
+    pub error: Style,
+    /// `warning`, and primary labels of warnings
+    pub warning: Style,
+    /// `note`, and primary labels of notes
+    pub note: Style,
+    /// `help`, and primary labels of help
+    pub help: Style,
+    /// Message after the severity
+    pub message: Style,
+    /// Line numbers, `|`, `-->`, and `=`
+    pub gutter: Style,
+    /// Lines of source code
+    pub source: Style,
+    /// Secondary labels
+    pub secondary: Style,
+}
+
+impl Default for DiagnosticTheme {
+    fn default() -> Self {
+        Self {
+            error: Style::new().color(Red).bold(),
+            warning: Style::new().color(Yellow).bold(),
+            note: Style::new().color(Green).bold(),
+            help: Style::new().color(Cyan).bold(),
+            message: Style::new().bold(),
+            gutter: Style::new().color(Blue).bold(),
+            source: Style::new(),
+            secondary: Style::new().color(Blue).bold(),
+        }
+    }
+}
+
+impl DiagnosticTheme {
+    /// Get the `Style` for a `Severity`
+    pub fn severity(&self, severity: Severity) -> Style {
+        match severity {
+            Severity::Error => self.error,
+            Severity::Warning => self.warning,
+            Severity::Note => self.note,
+            Severity::Help => self.help,
+        }
+    }
+}
+
+/// Message about source code, like the errors and warnings of rustc
+///
+/// The location is of the first primary label, or the first label if none are primary.
+/// Tabs in the source code are written as 4 spaces.
+///
+/// Create with `Diagnostic::error(message)`, `Diagnostic::warning(message)`, or `Diagnostic::new`
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{Diagnostic, DiagnosticTheme, Label};
+/// let source = "let x: u8 = \"a\";\n";
+///
+/// let diagnostic = Diagnostic::error("mismatched types")
+///     .code("E0308")
+///     .source("main.dsl", source)
+///     .label(Label::primary(12..15, "expected `u8`, found `&str`"))
+///     .label(Label::secondary(7..9, "expected due to this"))
+///     .help("remove the quotes");
+///
+/// println!("{}", diagnostic.render(&DiagnosticTheme::default()));
+/// assert_eq!(
+///     diagnostic.render(&DiagnosticTheme::default()).plain(),
+///     "\
+/// error[E0308]: mismatched types
+///  --> main.dsl:1:13
+///   |
+/// 1 | let x: u8 = \"a\";
+///   |        --   ^^^ expected `u8`, found `&str`
+///   |        |
+///   |        expected due to this
+///   |
+///   = help: remove the quotes"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    severity: Severity,
+    message: String,
+    code: Option<String>,
+    source: Option<(String, String)>,
+    labels: Vec<Label>,
+    footers: Vec<(Severity, String)>,
+}
+
+impl Diagnostic {
+    /// Create a `Diagnostic` with a `Severity` and message
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            code: None,
+            source: None,
+            labels: Vec::new(),
+            footers: Vec::new(),
+        }
+    }
+
+    /// Create an error
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    /// Create a warning
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    /// Add a code, such as `E0308`
+    pub fn code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Add the file name and source code, for the location and labels
+    pub fn source(mut self, name: impl Into<String>, source: impl Into<String>) -> Self {
+        self.source = Some((name.into(), source.into()));
+        self
+    }
+
+    /// Add a `Label` under part of the source code
+    pub fn label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    /// Add a note at the end
+    pub fn note(mut self, message: impl Into<String>) -> Self {
+        self.footers.push((Severity::Note, message.into()));
+        self
+    }
+
+    /// Add help at the end
+    pub fn help(mut self, message: impl Into<String>) -> Self {
+        self.footers.push((Severity::Help, message.into()));
+        self
+    }
+
+    /// Format the diagnostic with a `DiagnosticTheme`, with lines separated by newlines
+    pub fn render(&self, theme: &DiagnosticTheme) -> StyledString {
+        let severity = theme.severity(self.severity);
+        let mut header = StyledString::new();
+        match &self.code {
+            Some(code) => header.push(format!("{}[{code}]", self.severity), severity),
+            None => header.push(self.severity.to_string(), severity),
+        }
+        header.push(format!(": {}", self.message), theme.message);
+        let mut lines = vec![header];
+
+        let snippet = self
+            .source
+            .as_ref()
+            .map(|(_, source)| Snippet::new(source, &self.labels));
+        let width = match &snippet {
+            Some(snippet) => snippet.gutter_width(),
+            None => 1,
+        };
+        let gutter = |text: &str| {
+            let mut line = StyledString::new();
+            line.push(format!("{text:>width$} |"), theme.gutter);
+            line
+        };
+
+        if let (Some((name, _)), Some(snippet)) = (&self.source, &snippet) {
+            let mut line = StyledString::new();
+            line.push(format!("{:width$}--> ", ""), theme.gutter);
+            match snippet.location {
+                Some((row, column)) => {
+                    line.push_plain(format!("{name}:{}:{}", row + 1, column + 1))
+                }
+                None => line.push_plain(name.as_str()),
+            }
+            lines.push(line);
+            snippet.write_lines(&mut lines, theme, self.severity, &gutter);
+        }
+
+        if !self.footers.is_empty() && snippet.is_some_and(|snippet| !snippet.segments.is_empty()) {
+            lines.push(gutter(""));
+        }
+        for (severity, message) in &self.footers {
+            let mut message_lines = message.split('\n');
+            let mut line = StyledString::new();
+            line.push(format!("{:width$} = ", ""), theme.gutter);
+            line.push(format!("{severity}:"), theme.message);
+            line.push_plain(format!(" {}", message_lines.next().unwrap_or_default()));
+            lines.push(line);
+
+            // Lines after the first are under the start of the message, with no spaces if empty
+            let indent = width + format!(" = {severity}: ").len();
+            for message_line in message_lines {
+                let mut line = StyledString::new();
+                if !message_line.is_empty() {
+                    line.push_plain(format!("{:indent$}{message_line}", ""));
+                }
+                lines.push(line);
+            }
+        }
+
+        let mut output = StyledString::new();
+        for (i, line) in lines.into_iter().enumerate() {
+            if i > 0 {
+                output.push_plain("\n");
+            }
+            output.append(line);
+        }
+        output
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render(&DiagnosticTheme::default()))
+    }
+}
+
+/// Part of a label on one line, with terminal columns, and tabs expanded
+#[derive(Debug, Clone, Copy)]
+struct Segment<'a> {
+    row: usize,
+    start: usize,
+    end: usize,
+    primary: bool,
+    /// Message of the label, on the last line of the label only
+    message: Option<&'a str>,
+}
+
+/// Lines of source code with labels
+struct Snippet<'a> {
+    lines: Vec<&'a str>,
+    segments: Vec<Segment<'a>>,
+    /// Start of the first primary label, or the first label
+    location: Option<(usize, usize)>,
+}
+
+impl<'a> Snippet<'a> {
+    fn new(source: &'a str, labels: &'a [Label]) -> Self {
+        let lines: Vec<_> = source
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .collect();
+        let mut segments = Vec::new();
+
+        for label in labels {
+            let (start_row, start_column) = position(source, label.range.start);
+            let (end_row, end_column) = position(source, label.range.end.max(label.range.start));
+
+            for (row, line) in lines.iter().enumerate().take(end_row + 1).skip(start_row) {
+                let start = if row == start_row {
+                    display_column(line, start_column)
+                } else {
+                    0
+                };
+                let end = if row == end_row {
+                    display_column(line, end_column)
+                } else {
+                    display_column(line, usize::MAX)
+                };
+                segments.push(Segment {
+                    row,
+                    start,
+                    // Empty labels point at one character
+                    end: end.max(start + 1),
+                    primary: label.primary,
+                    message: (row == end_row).then_some(label.message.as_str()),
+                });
+            }
+        }
+
+        let location = labels
+            .iter()
+            .find(|label| label.primary)
+            .or(labels.first())
+            .map(|label| position(source, label.range.start));
+
+        Self {
+            lines,
+            segments,
+            location,
+        }
+    }
+
+    /// Get the width of the largest line number
+    fn gutter_width(&self) -> usize {
+        let max_row = self
+            .segments
+            .iter()
+            .map(|segment| segment.row + 1)
+            .max()
+            .unwrap_or(1);
+        max_row.to_string().len()
+    }
+
+    /// Write each line with labels, with `...` between lines which are not next to each other
+    fn write_lines(
+        &self,
+        output: &mut Vec<StyledString>,
+        theme: &DiagnosticTheme,
+        severity: Severity,
+        gutter: &dyn Fn(&str) -> StyledString,
+    ) {
+        let label_style = |primary: bool| {
+            if primary {
+                theme.severity(severity)
+            } else {
+                theme.secondary
+            }
+        };
+
+        let mut rows: Vec<_> = self.segments.iter().map(|segment| segment.row).collect();
+        rows.sort_unstable();
+        rows.dedup();
+
+        if rows.is_empty() {
+            return;
+        }
+        output.push(gutter(""));
+        for (i, &row) in rows.iter().enumerate() {
+            if i > 0 && row > rows[i - 1] + 1 {
+                let mut line = StyledString::new();
+                line.push("...", theme.gutter);
+                output.push(line);
+            }
+
+            let mut line = gutter(&(row + 1).to_string());
+            line.push_plain(" ");
+            line.push(
+                self.lines[row].replace('\t', &" ".repeat(TAB_WIDTH)),
+                theme.source,
+            );
+            output.push(line);
+
+            let mut segments: Vec<_> = self
+                .segments
+                .iter()
+                .filter(|segment| segment.row == row)
+                .collect();
+            segments.sort_by_key(|segment| segment.start);
+
+            // Underlines, with primary labels on top
+            let mut line = gutter("");
+            line.push_plain(" ");
+            let end = segments
+                .iter()
+                .map(|segment| segment.end)
+                .max()
+                .unwrap_or(0);
+            for column in 0..end {
+                let segment = segments
+                    .iter()
+                    .filter(|segment| (segment.start..segment.end).contains(&column))
+                    .max_by_key(|segment| segment.primary);
+                match segment {
+                    Some(segment) if segment.primary => line.push("^", label_style(true)),
+                    Some(_) => line.push("-", label_style(false)),
+                    None => line.push_plain(" "),
+                }
+            }
+
+            // Message of the last label is on the same line, others are below
+            let mut messages: Vec<_> = segments
+                .iter()
+                .filter_map(|segment| match segment.message {
+                    Some(message) if !message.is_empty() => {
+                        Some((segment.start, message, label_style(segment.primary)))
+                    }
+                    _ => None,
+                })
+                .collect();
+            let mut inline = None;
+            if let Some(&(start, message, style)) = messages.last() {
+                if segments
+                    .last()
+                    .is_some_and(|segment| segment.start == start)
+                {
+                    inline = Some((end + 1, message, style));
+                    messages.pop();
+                }
+            }
+            let pipes: Vec<_> = messages
+                .iter()
+                .map(|&(start, _, style)| (start, "|", style))
+                .collect();
+
+            // Lines of messages after the first are in the same column, with the pipes of the
+            // messages before
+            if let Some((column, message, style)) = inline {
+                let mut message_lines = message.split('\n');
+                line.push(format!(" {}", message_lines.next().unwrap_or_default()), style);
+                output.push(line);
+                for message_line in message_lines {
+                    let mut items = pipes.clone();
+                    items.push((column, message_line, style));
+                    output.push(columns(gutter(""), &items));
+                }
+            } else {
+                output.push(line);
+            }
+
+            for i in (0..messages.len()).rev() {
+                output.push(columns(gutter(""), &pipes[..=i]));
+
+                let (start, message, style) = messages[i];
+                for message_line in message.split('\n') {
+                    let mut items = pipes[..i].to_vec();
+                    items.push((start, message_line, style));
+                    output.push(columns(gutter(""), &items));
+                }
+            }
+        }
+    }
+}
+
+/// Get the row and column of a byte offset, counted in characters
+///
+/// Offsets past the end, or inside a character, are moved back
+fn position(source: &str, offset: usize) -> (usize, usize) {
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    let before = &source[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count(),
+        before[line_start..].chars().count(),
+    )
+}
+
+/// Get the terminal column of a character in a line, with tabs expanded
+fn display_column(line: &str, column: usize) -> usize {
+    line.chars()
+        .take(column)
+        .map(|ch| match ch {
+            '\t' => TAB_WIDTH,
+            _ => ch.width().unwrap_or(0),
+        })
+        .sum()
+}
+
+/// Write text at columns after the gutter, in order of column
+fn columns(mut line: StyledString, items: &[(usize, &str, Style)]) -> StyledString {
+    let mut column = 0;
+    line.push_plain(" ");
+    for &(start, text, style) in items {
+        line.push_plain(" ".repeat(start.saturating_sub(column)));
+        line.push(text, style);
+        column = start + text.width();
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style;
+
+    fn plain(diagnostic: &Diagnostic) -> String {
+        diagnostic.render(&DiagnosticTheme::default()).plain()
+    }
+
+    #[test]
+    fn render_works() {
+        assert_eq!(plain(&Diagnostic::warning("unused")), "warning: unused");
+        assert_eq!(
+            plain(&Diagnostic::error("failed").code("E1").note("a").help("b")),
+            "error[E1]: failed\n  = note: a\n  = help: b"
+        );
+        assert_eq!(
+            plain(&Diagnostic::new(Severity::Note, "x").source("a.txt", "abc")),
+            "note: x\n --> a.txt"
+        );
+    }
+
+    #[test]
+    fn render_works_w_multiline_footers() {
+        let diagnostic = Diagnostic::error("e")
+            .note("a\nb")
+            .help("c\n\nd");
+        assert_eq!(
+            plain(&diagnostic),
+            "\
+error: e
+  = note: a
+          b
+  = help: c
+
+          d"
+        );
+    }
+
+    #[test]
+    fn render_works_w_labels() {
+        let source = "fn main() {\n    let x = 1;\n\n\n\n\n\n\n\n    x + y\n}\n";
+        let diagnostic = Diagnostic::error("unknown name")
+            .source("main.dsl", source)
+            .label(Label::secondary(16..21, "defined here"))
+            .label(Label::primary(
+                source.find('y').unwrap()..source.find('y').unwrap() + 1,
+                "not found",
+            ))
+            .label(Label::secondary(
+                source.find("x +").unwrap()..source.find("x +").unwrap() + 3,
+                "",
+            ));
+
+        assert_eq!(
+            plain(&diagnostic),
+            "\
+error: unknown name
+  --> main.dsl:10:9
+   |
+ 2 |     let x = 1;
+   |     ----- defined here
+...
+10 |     x + y
+   |     --- ^ not found"
+        );
+    }
+
+    #[test]
+    fn render_works_w_many_labels_on_line() {
+        let diagnostic = Diagnostic::error("e")
+            .source("a", "abc def ghi")
+            .label(Label::primary(0..3, "one"))
+            .label(Label::secondary(4..7, "two"))
+            .label(Label::secondary(8..11, ""))
+            .note("n");
+
+        assert_eq!(
+            plain(&diagnostic),
+            "\
+error: e
+ --> a:1:1
+  |
+1 | abc def ghi
+  | ^^^ --- ---
+  | |   |
+  | |   two
+  | |
+  | one
+  |
+  = note: n"
+        );
+    }
+
+    #[test]
+    fn render_works_w_multiline_labels() {
+        let diagnostic = Diagnostic::error("e")
+            .source("a", "ab\r\ncd\né")
+            .label(Label::primary(1..6, "x"))
+            .label(Label::secondary(8..8, "end"))
+            .label(Label::secondary(100..200, "past end"));
+
+        assert_eq!(
+            plain(&diagnostic),
+            "\
+error: e
+ --> a:1:2
+  |
+1 | ab
+  |  ^
+2 | cd
+  | ^^ x
+3 | é
+  | -- past end
+  | |
+  | end"
+        );
+    }
+
+    #[test]
+    fn render_works_w_tabs() {
+        let diagnostic = Diagnostic::error("e")
+            .source("a", "{\n\tlet\tx = 1;\n}")
+            .label(Label::primary(7..8, "x"))
+            .label(Label::secondary(3..6, ""));
+
+        assert_eq!(
+            plain(&diagnostic),
+            "\
+error: e
+ --> a:2:6
+  |
+2 |     let    x = 1;
+  |     ---    ^ x"
+        );
+
+        // Wide characters take 2 columns
+        let source = "名前 = x";
+        let diagnostic = Diagnostic::error("e")
+            .source("a", source)
+            .label(Label::primary(0..6, ""))
+            .label(Label::secondary(source.find('x').unwrap()..source.len(), "y"));
+        assert_eq!(
+            plain(&diagnostic),
+            "error: e\n --> a:1:1\n  |\n1 | 名前 = x\n  | ^^^^   - y"
+        );
+    }
+
+    #[test]
+    fn render_works_w_multiline_messages() {
+        let diagnostic = Diagnostic::error("e")
+            .source("a", "abc def")
+            .label(Label::primary(0..3, "one\nmore"))
+            .label(Label::secondary(4..7, "two\nlines"));
+
+        assert_eq!(
+            plain(&diagnostic),
+            "\
+error: e
+ --> a:1:1
+  |
+1 | abc def
+  | ^^^ --- two
+  | |       lines
+  | |
+  | one
+  | more"
+        );
+    }
+
+    #[test]
+    fn render_works_w_theme() {
+        let theme = DiagnosticTheme {
+            error: style!(Red),
+            message: style!(),
+            gutter: style!(Blue),
+            secondary: style!(Green),
+            ..Default::default()
+        };
+        let diagnostic = Diagnostic::error("e")
+            .source("a", "ab")
+            .label(Label::primary(0..1, "x"))
+            .label(Label::secondary(1..2, ""));
+
+        assert_eq!(
+            diagnostic.render(&theme).to_string(),
+            "\x1b[31merror\x1b[0m: e\n\
+            \x1b[34m --> \x1b[0ma:1:1\n\
+            \x1b[34m  |\x1b[0m\n\
+            \x1b[34m1 |\x1b[0m ab\n\
+            \x1b[34m  |\x1b[0m \x1b[31m^\x1b[0m\x1b[32m-\x1b[0m\n\
+            \x1b[34m  |\x1b[0m \x1b[31m|\x1b[0m\n\
+            \x1b[34m  |\x1b[0m \x1b[31mx\x1b[0m"
+        );
+        assert_eq!(Severity::Help.to_string(), "help");
+    }
+}
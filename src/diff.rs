@@ -0,0 +1,588 @@
+use std::ops::Range;
+
+use crate::{Color::*, Style, StyledString};
+
+/// Change to a line, from `diff_lines`
+///
+/// Lines include the trailing newline, if any
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change<'a> {
+    /// Line is in both texts
+    Equal(&'a str),
+    /// Line is only in the old text
+    Delete(&'a str),
+    /// Line is only in the new text
+    Insert(&'a str),
+}
+
+impl<'a> Change<'a> {
+    /// Get the line of the change
+    pub fn line(self) -> &'a str {
+        match self {
+            Change::Equal(line) | Change::Delete(line) | Change::Insert(line) => line,
+        }
+    }
+}
+
+/// Compare the lines of two texts, with the Myers diff algorithm
+///
+/// Returns a shortest list of changes, with deletions before insertions
+///
+/// # Examples
+///
+/// ```
+/// use stilo::{diff_lines, Change};
+///
+/// let old = "name = \"stilo\"\nversion = 1\n";
+/// let new = "name = \"stilo\"\nversion = 2\n";
+///
+/// assert_eq!(
+///     diff_lines(old, new),
+///     [
+///         Change::Equal("name = \"stilo\"\n"),
+///         Change::Delete("version = 1\n"),
+///         Change::Insert("version = 2\n"),
+///     ]
+/// );
+/// ```
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<Change<'a>> {
+    let old_lines: Vec<_> = old.split_inclusive('\n').collect();
+    let new_lines: Vec<_> = new.split_inclusive('\n').collect();
+
+    let (mut x, mut y) = (0, 0);
+    diff(&old_lines, &new_lines)
+        .into_iter()
+        .map(|edit| match edit {
+            Edit::Equal => {
+                x += 1;
+                y += 1;
+                Change::Equal(old_lines[x - 1])
+            }
+            Edit::Delete => {
+                x += 1;
+                Change::Delete(old_lines[x - 1])
+            }
+            Edit::Insert => {
+                y += 1;
+                Change::Insert(new_lines[y - 1])
+            }
+        })
+        .collect()
+}
+
+/// Edit of one item, from `diff`
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Edit {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Find the shortest edits from `old` to `new`, with the Myers diff algorithm
+fn diff<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Edit> {
+    // Common start and end are skipped, which is faster for small changes
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut edits = vec![Edit::Equal; prefix];
+    edits.extend(myers(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    ));
+    edits.extend(vec![Edit::Equal; suffix]);
+    edits
+}
+
+/// Myers diff algorithm, which keeps each step to walk back along the shortest path
+///
+/// Only the diagonals reachable at each step are kept, so memory is `O(D²)` for `D` edits
+fn myers<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Edit> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let offset = n + m;
+    // Furthest `x` reached on each diagonal `k = x - y`
+    let mut v = vec![0; 2 * offset as usize + 2];
+    // Diagonals `-d..=d + 1` of `v` before each step `d`, where `d + 1` is the start for `d = 0`
+    let mut trace = Vec::new();
+
+    let index = |k: isize| (k + offset) as usize;
+    // Move down (insert) to diagonal `k`, instead of right (delete)
+    // `at` gets `x` on a diagonal from the previous step
+    let down = |at: &dyn Fn(isize) -> isize, d: isize, k: isize| {
+        k == -d || (k != d && at(k - 1) < at(k + 1))
+    };
+
+    'search: for d in 0..=offset {
+        trace.push(v[index(-d)..=index(d + 1)].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if down(&|k| v[index(k)], d, k) {
+                v[index(k + 1)]
+            } else {
+                v[index(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[index(k)] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let at = |k: isize| v[(k + d) as usize];
+        let k = x - y;
+        let prev_k = if down(&at, d, k) { k + 1 } else { k - 1 };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            edits.push(Edit::Equal);
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            edits.push(if x == prev_x {
+                Edit::Insert
+            } else {
+                Edit::Delete
+            });
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+
+    edits.reverse();
+    edits
+}
+
+/// Format a unified diff, with a `Style` for each kind of line
+///
+/// With `word_diff`, changed words are highlighted in lines which were replaced.
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{Color::*, DiffFormatter, Style};
+/// let formatter = DiffFormatter {
+///     context_lines: 1,
+///     ..Default::default()
+/// };
+///
+/// let text = formatter.format("a\nb\nc\nd\n", "a\nb\nC\nd\n");
+/// println!("{}", text);
+/// assert_eq!(text.plain(), "@@ -2,3 +2,3 @@\n b\n-c\n+C\n d\n");
+///
+/// let formatter = DiffFormatter {
+///     word_diff: true,
+///     added_word: Style::new().color(Black).background(Green),
+///     removed_word: Style::new().color(Black).background(Red),
+///     ..Default::default()
+/// };
+/// println!("{}", formatter.format("let x = 1;\n", "let y = 1;\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffFormatter {
+    /// Number of unchanged lines around each change
+    pub context_lines: usize,
+    /// Highlight changed words in replaced lines
+    pub word_diff: bool,
+    /// File names, as `---` and `+++` lines
+    pub header: Style,
+    /// Line numbers of each hunk, as `@@` lines
+    pub hunk_header: Style,
+    /// Unchanged lines
+    pub context: Style,
+    /// Added lines
+    pub added: Style,
+    /// Removed lines
+    pub removed: Style,
+    /// Changed words in added lines, applied on top of `added`
+    pub added_word: Style,
+    /// Changed words in removed lines, applied on top of `removed`
+    pub removed_word: Style,
+}
+
+impl Default for DiffFormatter {
+    fn default() -> Self {
+        Self {
+            context_lines: 3,
+            word_diff: false,
+            header: Style::new().bold(),
+            hunk_header: Style::new().color(Cyan),
+            context: Style::new(),
+            added: Style::new().color(Green),
+            removed: Style::new().color(Red),
+            added_word: Style::new().reverse(),
+            removed_word: Style::new().reverse(),
+        }
+    }
+}
+
+impl DiffFormatter {
+    /// Create a `DiffFormatter` with 3 lines of context and the default styles
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Format a unified diff of two texts, with file names
+    ///
+    /// Empty if the texts are equal
+    pub fn format_files(
+        &self,
+        old_name: &str,
+        new_name: &str,
+        old: &str,
+        new: &str,
+    ) -> StyledString {
+        let hunks = self.format(old, new);
+        if hunks.is_empty() {
+            return hunks;
+        }
+
+        let mut output = StyledString::new();
+        output.push(format!("--- {old_name}"), self.header);
+        output.push_plain("\n");
+        output.push(format!("+++ {new_name}"), self.header);
+        output.push_plain("\n");
+        output.append(hunks);
+        output
+    }
+
+    /// Format a unified diff of two texts, without file names
+    ///
+    /// Empty if the texts are equal
+    pub fn format(&self, old: &str, new: &str) -> StyledString {
+        let changes = diff_lines(old, new);
+        let mut output = StyledString::new();
+
+        for hunk in self.hunks(&changes) {
+            self.write_hunk(&mut output, &changes, hunk);
+        }
+        output
+    }
+
+    /// Find ranges of changes to show, with context, joining hunks which overlap
+    fn hunks(&self, changes: &[Change]) -> Vec<Range<usize>> {
+        let is_equal = |change: &Change| matches!(change, Change::Equal(_));
+        let mut hunks = Vec::new();
+        let mut i = 0;
+
+        while i < changes.len() {
+            if is_equal(&changes[i]) {
+                i += 1;
+                continue;
+            }
+
+            let start = i.saturating_sub(self.context_lines);
+            let mut end = i;
+            loop {
+                end += changes[end..].iter().take_while(|c| !is_equal(c)).count();
+                let equal = changes[end..].iter().take_while(|c| is_equal(c)).count();
+
+                if end + equal == changes.len() || equal > 2 * self.context_lines {
+                    end += equal.min(self.context_lines);
+                    break;
+                }
+                end += equal;
+            }
+
+            hunks.push(start..end);
+            i = end;
+        }
+
+        hunks
+    }
+
+    /// Write a hunk header and its lines
+    fn write_hunk(&self, output: &mut StyledString, changes: &[Change], hunk: Range<usize>) {
+        let count = |changes: &[Change], new: bool| {
+            changes
+                .iter()
+                .filter(|change| match change {
+                    Change::Equal(_) => true,
+                    Change::Delete(_) => !new,
+                    Change::Insert(_) => new,
+                })
+                .count()
+        };
+        // Empty ranges start at the line before
+        let range = |start: usize, len: usize| match len {
+            0 => format!("{start},0"),
+            1 => format!("{}", start + 1),
+            _ => format!("{},{len}", start + 1),
+        };
+
+        let before = &changes[..hunk.start];
+        let lines = &changes[hunk];
+        output.push(
+            format!(
+                "@@ -{} +{} @@",
+                range(count(before, false), count(lines, false)),
+                range(count(before, true), count(lines, true)),
+            ),
+            self.hunk_header,
+        );
+        output.push_plain("\n");
+
+        let mut i = 0;
+        while i < lines.len() {
+            if let Change::Equal(line) = lines[i] {
+                self.write_line(output, ' ', self.context, &[(line, false)]);
+                i += 1;
+                continue;
+            }
+
+            let deleted: Vec<_> = lines[i..]
+                .iter()
+                .map_while(|change| match change {
+                    Change::Delete(line) => Some(*line),
+                    _ => None,
+                })
+                .collect();
+            let inserted: Vec<_> = lines[i + deleted.len()..]
+                .iter()
+                .map_while(|change| match change {
+                    Change::Insert(line) => Some(*line),
+                    _ => None,
+                })
+                .collect();
+            i += deleted.len() + inserted.len();
+
+            // Replaced lines are paired in order, to compare words
+            let pairs: Vec<_> = deleted
+                .iter()
+                .zip(&inserted)
+                .map(|(old, new)| {
+                    if self.word_diff {
+                        diff_words(old, new)
+                    } else {
+                        (vec![(*old, false)], vec![(*new, false)])
+                    }
+                })
+                .collect();
+
+            for (j, line) in deleted.iter().enumerate() {
+                match pairs.get(j) {
+                    Some((words, _)) => self.write_line(output, '-', self.removed, words),
+                    None => self.write_line(output, '-', self.removed, &[(line, false)]),
+                }
+            }
+            for (j, line) in inserted.iter().enumerate() {
+                match pairs.get(j) {
+                    Some((_, words)) => self.write_line(output, '+', self.added, words),
+                    None => self.write_line(output, '+', self.added, &[(line, false)]),
+                }
+            }
+        }
+    }
+
+    /// Write a line, made of words which may be changed
+    fn write_line(
+        &self,
+        output: &mut StyledString,
+        marker: char,
+        style: Style,
+        words: &[(&str, bool)],
+    ) {
+        let word_style = if marker == '+' {
+            style.merge(self.added_word)
+        } else {
+            style.merge(self.removed_word)
+        };
+
+        output.push(marker.to_string(), style);
+        let mut has_newline = false;
+        for (word, changed) in words {
+            let word = match word.strip_suffix('\n') {
+                Some(word) => {
+                    has_newline = true;
+                    word
+                }
+                None => word,
+            };
+            output.push(word, if *changed { word_style } else { style });
+        }
+        output.push_plain("\n");
+
+        if !has_newline {
+            output.push_plain("\\ No newline at end of file\n");
+        }
+    }
+}
+
+/// Words of a line, and if each word changed
+type Words<'a> = Vec<(&'a str, bool)>;
+
+/// Compare the words of two lines, marking which words changed
+fn diff_words<'a>(old: &'a str, new: &'a str) -> (Words<'a>, Words<'a>) {
+    let old_words = split_words(old);
+    let new_words = split_words(new);
+    let (mut old_iter, mut new_iter) = (old_words.iter(), new_words.iter());
+    let (mut old_marked, mut new_marked) = (Vec::new(), Vec::new());
+
+    for edit in diff(&old_words, &new_words) {
+        match edit {
+            Edit::Equal => {
+                old_marked.push((*old_iter.next().unwrap(), false));
+                new_marked.push((*new_iter.next().unwrap(), false));
+            }
+            Edit::Delete => old_marked.push((*old_iter.next().unwrap(), true)),
+            Edit::Insert => new_marked.push((*new_iter.next().unwrap(), true)),
+        }
+    }
+
+    (old_marked, new_marked)
+}
+
+/// Split text into words, runs of whitespace, and single other characters
+fn split_words(text: &str) -> Vec<&str> {
+    let kind = |ch: char| {
+        if ch.is_alphanumeric() || ch == '_' {
+            0
+        } else if ch.is_whitespace() {
+            1
+        } else {
+            2
+        }
+    };
+
+    let mut words = Vec::new();
+    let mut rest = text;
+    while let Some(first) = rest.chars().next() {
+        let len = match kind(first) {
+            2 => first.len_utf8(),
+            first_kind => rest.find(|ch| kind(ch) != first_kind).unwrap_or(rest.len()),
+        };
+        words.push(&rest[..len]);
+        rest = &rest[len..];
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Change::*, *};
+    use crate::style;
+
+    #[test]
+    fn diff_lines_works() {
+        assert_eq!(diff_lines("", ""), []);
+        assert_eq!(diff_lines("a\n", "a\n"), [Equal("a\n")]);
+        assert_eq!(diff_lines("", "a\n"), [Insert("a\n")]);
+        assert_eq!(diff_lines("a", "a\n"), [Delete("a"), Insert("a\n")]);
+        assert_eq!(
+            diff_lines("a\nb\nc\na\nb\nb\na\n", "c\nb\na\nb\na\nc\n"),
+            [
+                Delete("a\n"),
+                Delete("b\n"),
+                Equal("c\n"),
+                Insert("b\n"),
+                Equal("a\n"),
+                Equal("b\n"),
+                Delete("b\n"),
+                Equal("a\n"),
+                Insert("c\n"),
+            ]
+        );
+
+        // Changes join to the original texts
+        let (old, new) = ("x\ny\nz\nw\n", "y\nw\nv\nx\n");
+        let changes = diff_lines(old, new);
+        let join = |kind: fn(&Change) -> bool| {
+            changes
+                .iter()
+                .filter(|change| kind(change))
+                .map(|change| change.line())
+                .collect::<String>()
+        };
+        assert_eq!(join(|change| !matches!(change, Insert(_))), old);
+        assert_eq!(join(|change| !matches!(change, Delete(_))), new);
+    }
+
+    #[test]
+    fn format_works() {
+        let formatter = DiffFormatter {
+            context_lines: 1,
+            ..Default::default()
+        };
+
+        assert!(formatter.format("a\nb\n", "a\nb\n").is_empty());
+        assert!(formatter.format_files("a", "b", "", "").is_empty());
+
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n";
+        let new = "1\n2\nthree\n4\n5\n6\n8\nnine\n";
+        assert_eq!(
+            formatter.format(old, new).plain(),
+            "@@ -2,3 +2,3 @@\n 2\n-3\n+three\n 4\n@@ -6,3 +6,3 @@\n 6\n-7\n 8\n+nine\n"
+        );
+
+        // Hunks with close changes are joined
+        let new = "1\n2\nthree\n4\n5\nsix\n7\n8\n";
+        assert_eq!(
+            formatter.format(old, new).plain(),
+            "@@ -2,6 +2,6 @@\n 2\n-3\n+three\n 4\n 5\n-6\n+six\n 7\n"
+        );
+
+        assert_eq!(
+            formatter.format_files("a/x", "b/x", "", "a\nb").plain(),
+            "--- a/x\n+++ b/x\n@@ -0,0 +1,2 @@\n+a\n+b\n\\ No newline at end of file\n"
+        );
+        assert_eq!(
+            formatter.format("a\nb\n", "b\n").plain(),
+            "@@ -1,2 +1 @@\n-a\n b\n"
+        );
+    }
+
+    #[test]
+    fn format_works_w_styles() {
+        let formatter = DiffFormatter {
+            context: style!(+dim),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            formatter.format("a\nb\n", "a\nc\n").to_string(),
+            "\x1b[36m@@ -1,2 +1,2 @@\x1b[0m\n\x1b[2m a\x1b[0m\n\x1b[31m-b\x1b[0m\n\x1b[32m+c\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn format_works_w_word_diff() {
+        let formatter = DiffFormatter {
+            word_diff: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            formatter.format("let x = 1;\n", "let y = 1;\n").to_string(),
+            "\x1b[36m@@ -1 +1 @@\x1b[0m\n\
+            \x1b[31m-let \x1b[0m\x1b[31;7mx\x1b[0m\x1b[31m = 1;\x1b[0m\n\
+            \x1b[32m+let \x1b[0m\x1b[32;7my\x1b[0m\x1b[32m = 1;\x1b[0m\n"
+        );
+
+        // Only replaced lines are compared
+        let text = formatter.format("a b\nc\n", "a c\n").to_string();
+        assert!(text.contains("\x1b[31m-c\x1b[0m\n"));
+        assert!(text.contains("\x1b[31m-a \x1b[0m\x1b[31;7mb\x1b[0m\n"));
+    }
+
+    #[test]
+    fn split_words_works() {
+        assert_eq!(
+            split_words("fn foo_1(a,  b) -> é"),
+            ["fn", " ", "foo_1", "(", "a", ",", "  ", "b", ")", " ", "-", ">", " ", "é"]
+        );
+        assert_eq!(split_words(""), Vec::<&str>::new());
+    }
+}
@@ -0,0 +1,166 @@
+use std::env;
+
+use crate::{Color::*, Style};
+
+/// Styles for search results, parsed from `GREP_COLORS`
+///
+/// Defaults are the same as GNU grep. Entries with invalid SGR parameters are ignored.
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{style, GrepColors, Color::*};
+/// let colors = GrepColors::parse("ms=01;32:fn=35");
+///
+/// assert_eq!(colors.selected_match, style!(Green + bold));
+/// assert_eq!(colors.context_match, style!(Red + bold));
+/// assert_eq!(colors.file_name, style!(Magenta));
+///
+/// // Read from environment
+/// let colors = GrepColors::from_env().unwrap_or_default();
+/// println!("{}", colors.file_name.format("src/main.rs"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrepColors {
+    /// Matching text in a selected line (`ms`)
+    pub selected_match: Style,
+    /// Matching text in a context line (`mc`)
+    pub context_match: Style,
+    /// Whole selected line (`sl`)
+    pub selected_line: Style,
+    /// Whole context line (`cx`)
+    pub context_line: Style,
+    /// File name prefix (`fn`)
+    pub file_name: Style,
+    /// Line number prefix (`ln`)
+    pub line_number: Style,
+    /// Byte offset prefix (`bn`)
+    pub byte_offset: Style,
+    /// Separator between prefixes and content (`se`)
+    pub separator: Style,
+    /// Swap `sl` and `cx` when matches are inverted (`rv`)
+    pub reverse: bool,
+    /// Do not clear to the end of line after styled text (`ne`)
+    pub no_erase: bool,
+}
+
+impl Default for GrepColors {
+    fn default() -> Self {
+        Self {
+            selected_match: Style::new().color(Red).bold(),
+            context_match: Style::new().color(Red).bold(),
+            selected_line: Style::new(),
+            context_line: Style::new(),
+            file_name: Style::new().color(Magenta),
+            line_number: Style::new().color(Green),
+            byte_offset: Style::new().color(Green),
+            separator: Style::new().color(Cyan),
+            reverse: false,
+            no_erase: false,
+        }
+    }
+}
+
+impl GrepColors {
+    /// Parse `GREP_COLORS` syntax, such as `ms=01;31:fn=35:rv`
+    ///
+    /// Starts with the default styles. `mt` sets both `ms` and `mc`
+    pub fn parse(input: &str) -> Self {
+        let mut colors = Self::default();
+
+        for entry in input.split(':') {
+            let Some((key, params)) = entry.split_once('=') else {
+                match entry {
+                    "rv" => colors.reverse = true,
+                    "ne" => colors.no_erase = true,
+                    _ => (),
+                }
+                continue;
+            };
+
+            let Ok(style) = Style::from_sgr(params) else {
+                continue;
+            };
+
+            match key {
+                "mt" => {
+                    colors.selected_match = style;
+                    colors.context_match = style;
+                }
+                "ms" => colors.selected_match = style,
+                "mc" => colors.context_match = style,
+                "sl" => colors.selected_line = style,
+                "cx" => colors.context_line = style,
+                "fn" => colors.file_name = style,
+                "ln" => colors.line_number = style,
+                "bn" => colors.byte_offset = style,
+                "se" => colors.separator = style,
+                _ => (),
+            }
+        }
+
+        colors
+    }
+
+    /// Parse the `GREP_COLORS` environment variable
+    ///
+    /// The deprecated `GREP_COLOR` variable is used for `mt`, if set.
+    /// Returns `None` if neither variable is set
+    pub fn from_env() -> Option<Self> {
+        let color = env::var("GREP_COLOR").ok();
+        let colors = env::var("GREP_COLORS").ok();
+
+        if color.is_none() && colors.is_none() {
+            return None;
+        }
+
+        let mut input = String::new();
+        if let Some(color) = color {
+            input += &format!("mt={color}:");
+        }
+        input += &colors.unwrap_or_default();
+
+        Some(Self::parse(&input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style;
+
+    #[test]
+    fn parse_works() {
+        assert_eq!(GrepColors::parse(""), GrepColors::default());
+
+        let colors = GrepColors::parse("ms=01;32:mc=33:sl=1:cx=2:fn=34:ln=35:bn=36:se=37");
+        assert_eq!(
+            colors,
+            GrepColors {
+                selected_match: style!(Green + bold),
+                context_match: style!(Yellow),
+                selected_line: style!(+bold),
+                context_line: style!(+dim),
+                file_name: style!(Blue),
+                line_number: style!(Magenta),
+                byte_offset: style!(Cyan),
+                separator: style!(White),
+                reverse: false,
+                no_erase: false,
+            }
+        );
+
+        let colors = GrepColors::parse("mt=04:rv:ne:fn=:xx=31:ln=bad");
+        assert_eq!(colors.selected_match, style!(+u));
+        assert_eq!(colors.context_match, style!(+u));
+        assert_eq!(colors.file_name, style!());
+        assert_eq!(colors.line_number, style!(Green));
+        assert!(colors.reverse);
+        assert!(colors.no_erase);
+
+        // Later entries override
+        let colors = GrepColors::parse("mt=31:ms=32");
+        assert_eq!(colors.selected_match, style!(Green));
+        assert_eq!(colors.context_match, style!(Red));
+    }
+}
@@ -0,0 +1,423 @@
+use super::{Highlighter, TokenKind};
+
+/// Pattern of text, matched by a `Rule`
+#[derive(Debug, Clone, Copy)]
+pub enum Pattern {
+    /// Exact text
+    Text(&'static str),
+    /// Any of the words, not followed by a letter, digit, or `_`
+    Words(&'static [&'static str]),
+    /// Any of the words ignoring ASCII case, not followed by a letter, digit, or `_`
+    WordsIgnoreCase(&'static [&'static str]),
+    /// One or more characters which match a function
+    Chars(fn(char) -> bool),
+    /// A character which matches the first function, then any characters which match the second
+    Word(fn(char) -> bool, fn(char) -> bool),
+    /// Text from an opening to a closing delimiter, with an optional escape character
+    ///
+    /// Continues to the end of the source code, if there is no closing delimiter
+    Delimited {
+        /// Opening delimiter
+        open: &'static str,
+        /// Closing delimiter
+        close: &'static str,
+        /// Character which skips the next character
+        escape: Option<char>,
+    },
+    /// Text from the start, to the end of the line
+    Line(&'static str),
+    /// Number, with an optional sign, decimal point, exponent, and suffix
+    Number,
+}
+
+impl Pattern {
+    /// Get the length in bytes of the matching text, at the start of the source code
+    fn match_len(&self, source: &str) -> Option<usize> {
+        let len = match *self {
+            Pattern::Text(text) => source.starts_with(text).then_some(text.len())?,
+
+            Pattern::Words(words) => words
+                .iter()
+                .find(|word| source.starts_with(**word) && is_boundary(&source[word.len()..]))?
+                .len(),
+
+            Pattern::WordsIgnoreCase(words) => words
+                .iter()
+                .find(|word| {
+                    source
+                        .get(..word.len())
+                        .is_some_and(|start| start.eq_ignore_ascii_case(word))
+                        && is_boundary(&source[word.len()..])
+                })?
+                .len(),
+
+            Pattern::Chars(matches) => len_while(source, matches),
+
+            Pattern::Word(first, rest) => {
+                let ch = source.chars().next().filter(|ch| first(*ch))?;
+                ch.len_utf8() + len_while(&source[ch.len_utf8()..], rest)
+            }
+
+            Pattern::Delimited {
+                open,
+                close,
+                escape,
+            } => {
+                let body = source.strip_prefix(open)?;
+                let mut chars = body.char_indices();
+                let mut end = body.len();
+                while let Some((i, ch)) = chars.next() {
+                    if Some(ch) == escape {
+                        chars.next();
+                    } else if body[i..].starts_with(close) {
+                        end = i + close.len();
+                        break;
+                    }
+                }
+                open.len() + end
+            }
+
+            Pattern::Line(start) => {
+                source.strip_prefix(start)?;
+                source.find('\n').unwrap_or(source.len())
+            }
+
+            Pattern::Number => number_len(source)?,
+        };
+        (len > 0).then_some(len)
+    }
+}
+
+/// Change of state after a `Rule` matches
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Action {
+    Stay,
+    Push(&'static str),
+    Pop,
+}
+
+/// Rule of a `Grammar`, which gives matching text a `TokenKind`
+///
+/// Create with `Rule::new(pattern, kind)`
+#[derive(Debug, Clone, Copy)]
+pub struct Rule {
+    pattern: Pattern,
+    kind: TokenKind,
+    before: Option<&'static str>,
+    action: Action,
+}
+
+impl Rule {
+    /// Create a `Rule`, which gives text matching a `Pattern` a `TokenKind`
+    pub fn new(pattern: Pattern, kind: TokenKind) -> Self {
+        Self {
+            pattern,
+            kind,
+            before: None,
+            action: Action::Stay,
+        }
+    }
+
+    /// Only match if followed by text, after any spaces or tabs
+    pub fn before(mut self, text: &'static str) -> Self {
+        self.before = Some(text);
+        self
+    }
+
+    /// Enter a state after matching
+    ///
+    /// If the `Grammar` has no state with the name, the state does not change
+    pub fn push(mut self, state: &'static str) -> Self {
+        self.action = Action::Push(state);
+        self
+    }
+
+    /// Return to the previous state after matching
+    pub fn pop(mut self) -> Self {
+        self.action = Action::Pop;
+        self
+    }
+
+    /// Get the length in bytes of the matching text, at the start of the source code
+    fn match_len(&self, source: &str) -> Option<usize> {
+        let len = self.pattern.match_len(source)?;
+        if let Some(before) = self.before {
+            let after = source[len..].trim_start_matches([' ', '\t']);
+            if !after.starts_with(before) {
+                return None;
+            }
+        }
+        Some(len)
+    }
+}
+
+/// Syntax highlighter made of states, each with a list of rules
+///
+/// The first state is used at the start. In each state, the first `Rule` which matches is used,
+/// and may change the state. Text which matches no rules is `TokenKind::Plain`.
+///
+/// # Examples
+///
+/// ```
+/// use stilo::highlight::{Grammar, Highlighter, Pattern, Rule, Theme, TokenKind};
+///
+/// // Shell commands
+/// let shell = Grammar::new()
+///     .state(
+///         "command",
+///         [
+///             Rule::new(Pattern::Line("#"), TokenKind::Comment),
+///             Rule::new(Pattern::Chars(char::is_whitespace), TokenKind::Plain),
+///             Rule::new(Pattern::Chars(|ch| !ch.is_whitespace()), TokenKind::Function)
+///                 .push("args"),
+///         ],
+///     )
+///     .state(
+///         "args",
+///         [
+///             Rule::new(Pattern::Text("\n"), TokenKind::Plain).pop(),
+///             Rule::new(Pattern::Text("|"), TokenKind::Operator).pop(),
+///             Rule::new(
+///                 Pattern::Word(|ch| ch == '-', |ch| !ch.is_whitespace()),
+///                 TokenKind::Attribute,
+///             ),
+///             Rule::new(
+///                 Pattern::Delimited { open: "\"", close: "\"", escape: Some('\\') },
+///                 TokenKind::String,
+///             ),
+///         ],
+///     );
+///
+/// let tokens = shell.tokens("ls -la | grep \"x\"");
+/// assert_eq!(tokens[0], (TokenKind::Function, "ls"));
+/// assert_eq!(tokens[2], (TokenKind::Attribute, "-la"));
+///
+/// println!("{}", shell.highlight("ls -la | grep \"x\"", &Theme::default()));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Grammar {
+    states: Vec<(&'static str, Vec<Rule>)>,
+}
+
+impl Grammar {
+    /// Create an empty `Grammar`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a state, with a name and a list of rules
+    pub fn state(mut self, name: &'static str, rules: impl IntoIterator<Item = Rule>) -> Self {
+        self.states.push((name, rules.into_iter().collect()));
+        self
+    }
+
+    /// Get the index of a state from its name, or `None` if no state has the name
+    fn state_index(&self, name: &str) -> Option<usize> {
+        self.states.iter().position(|(state, _)| *state == name)
+    }
+}
+
+impl Highlighter for Grammar {
+    /// Split source code into tokens
+    ///
+    /// A `Rule` which enters a state which does not exist stays in the current state
+    fn tokens<'a>(&self, source: &'a str) -> Vec<(TokenKind, &'a str)> {
+        let mut tokens = Vec::new();
+        let mut stack = vec![0];
+        let mut pos = 0;
+
+        while pos < source.len() {
+            let rest = &source[pos..];
+            let rules = self
+                .states
+                .get(*stack.last().expect("stack should not be empty"))
+                .map_or(&[][..], |(_, rules)| &rules[..]);
+
+            let matched = rules
+                .iter()
+                .find_map(|rule| Some((rule, rule.match_len(rest)?)));
+
+            let (kind, len) = match matched {
+                Some((rule, len)) => {
+                    match rule.action {
+                        Action::Stay => (),
+                        Action::Push(state) => {
+                            if let Some(index) = self.state_index(state) {
+                                stack.push(index);
+                            }
+                        }
+                        Action::Pop => {
+                            if stack.len() > 1 {
+                                stack.pop();
+                            }
+                        }
+                    }
+                    (rule.kind, len)
+                }
+                None => (
+                    TokenKind::Plain,
+                    rest.chars().next().map_or(1, char::len_utf8),
+                ),
+            };
+
+            tokens.push((kind, &rest[..len]));
+            pos += len;
+        }
+
+        tokens
+    }
+}
+
+/// Check if text does not start with a letter, digit, or `_`
+fn is_boundary(text: &str) -> bool {
+    !text
+        .chars()
+        .next()
+        .is_some_and(|ch| ch.is_alphanumeric() || ch == '_')
+}
+
+/// Get the length in bytes of the characters which match, at the start of text
+fn len_while(text: &str, matches: fn(char) -> bool) -> usize {
+    text.find(|ch| !matches(ch)).unwrap_or(text.len())
+}
+
+/// Get the length in bytes of a number, at the start of text
+fn number_len(text: &str) -> Option<usize> {
+    let digits = text.strip_prefix(['-', '+']).unwrap_or(text);
+    if !digits.starts_with(|ch: char| ch.is_ascii_digit()) {
+        return None;
+    }
+
+    let bytes = text.as_bytes();
+    let mut len = text.len() - digits.len();
+    while let Some(&byte) = bytes.get(len) {
+        let next_is_digit = bytes.get(len + 1).is_some_and(u8::is_ascii_digit);
+        let valid = match byte {
+            b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' | b'_' => true,
+            b'.' => next_is_digit,
+            // Exponent sign, such as `1e-5`
+            b'-' | b'+' => next_is_digit && matches!(bytes[len - 1], b'e' | b'E'),
+            _ => false,
+        };
+        if !valid {
+            break;
+        }
+        len += 1;
+    }
+    Some(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use TokenKind::*;
+
+    #[test]
+    fn patterns_work() {
+        let len = |pattern: Pattern, source| pattern.match_len(source);
+
+        assert_eq!(len(Pattern::Text("ab"), "abc"), Some(2));
+        assert_eq!(len(Pattern::Text("ab"), "ba"), None);
+        assert_eq!(len(Pattern::Text(""), "ab"), None);
+
+        assert_eq!(len(Pattern::Words(&["in", "int"]), "int x"), Some(3));
+        assert_eq!(len(Pattern::Words(&["in"]), "in x"), Some(2));
+        assert_eq!(len(Pattern::Words(&["in"]), "inner"), None);
+        assert_eq!(
+            len(Pattern::WordsIgnoreCase(&["select"]), "SELECT *"),
+            Some(6)
+        );
+        assert_eq!(len(Pattern::WordsIgnoreCase(&["sé"]), "s"), None);
+
+        assert_eq!(len(Pattern::Chars(char::is_alphabetic), "abc1"), Some(3));
+        assert_eq!(len(Pattern::Chars(char::is_alphabetic), "1abc"), None);
+        assert_eq!(
+            len(
+                Pattern::Word(|ch| ch == '$', char::is_alphanumeric),
+                "$HOME/x"
+            ),
+            Some(5)
+        );
+
+        let string = Pattern::Delimited {
+            open: "\"",
+            close: "\"",
+            escape: Some('\\'),
+        };
+        assert_eq!(len(string, r#""a\"b" c"#), Some(6));
+        assert_eq!(len(string, r#""abc"#), Some(4));
+        assert_eq!(len(string, "abc"), None);
+
+        assert_eq!(len(Pattern::Line("//"), "// a\nb"), Some(4));
+        assert_eq!(len(Pattern::Line("//"), "// a"), Some(4));
+
+        assert_eq!(len(Pattern::Number, "12, 3"), Some(2));
+        assert_eq!(len(Pattern::Number, "-1.5e-3]"), Some(7));
+        assert_eq!(len(Pattern::Number, "0xFF"), Some(4));
+        assert_eq!(len(Pattern::Number, "1.x"), Some(1));
+        assert_eq!(len(Pattern::Number, "-x"), None);
+    }
+
+    #[test]
+    fn grammar_works() {
+        let grammar = Grammar::new()
+            .state(
+                "root",
+                [
+                    Rule::new(Pattern::Words(&["let"]), Keyword),
+                    Rule::new(Pattern::Chars(char::is_alphabetic), Property).before("="),
+                    Rule::new(Pattern::Chars(char::is_alphabetic), Identifier),
+                    Rule::new(Pattern::Text("("), Punctuation).push("group"),
+                ],
+            )
+            .state(
+                "group",
+                [
+                    Rule::new(Pattern::Text(")"), Punctuation).pop(),
+                    Rule::new(Pattern::Number, Number),
+                ],
+            );
+
+        assert_eq!(
+            grammar.tokens("let a = b (1 c) 2 é"),
+            [
+                (Keyword, "let"),
+                (Plain, " "),
+                (Property, "a"),
+                (Plain, " "),
+                (Plain, "="),
+                (Plain, " "),
+                (Identifier, "b"),
+                (Plain, " "),
+                (Punctuation, "("),
+                (Number, "1"),
+                (Plain, " "),
+                (Plain, "c"),
+                (Punctuation, ")"),
+                (Plain, " "),
+                (Plain, "2"),
+                (Plain, " "),
+                (Identifier, "é"),
+            ]
+        );
+
+        // Extra closing bracket stays in the first state
+        assert_eq!(grammar.tokens("))"), [(Plain, ")"), (Plain, ")")]);
+        assert_eq!(Grammar::new().tokens("ab"), [(Plain, "a"), (Plain, "b")]);
+    }
+
+    #[test]
+    fn grammar_works_w_unknown_state() {
+        let grammar = Grammar::new().state(
+            "root",
+            [
+                Rule::new(Pattern::Text("("), Punctuation).push("group"),
+                Rule::new(Pattern::Text("x"), Keyword),
+            ],
+        );
+        assert_eq!(
+            grammar.tokens("(x"),
+            [(Punctuation, "("), (Keyword, "x")]
+        );
+    }
+}
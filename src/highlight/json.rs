@@ -0,0 +1,92 @@
+use super::{Grammar, Pattern, Rule, TokenKind::*};
+
+impl Grammar {
+    /// Grammar for JSON
+    ///
+    /// Object keys are `TokenKind::Property`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stilo::highlight::{Grammar, Highlighter, Theme};
+    /// let json = r#"{"name": "stilo", "tags": ["color", "ansi"], "stable": false}"#;
+    /// println!("{}", Grammar::json().highlight(json, &Theme::default()));
+    /// ```
+    pub fn json() -> Self {
+        let string = Pattern::Delimited {
+            open: "\"",
+            close: "\"",
+            escape: Some('\\'),
+        };
+
+        Grammar::new().state(
+            "value",
+            [
+                Rule::new(Pattern::Chars(char::is_whitespace), Plain),
+                Rule::new(string, Property).before(":"),
+                Rule::new(string, String),
+                Rule::new(Pattern::Words(&["true", "false", "null"]), Constant),
+                Rule::new(Pattern::Number, Number),
+                Rule::new(Pattern::Chars(|ch| "{}[],:".contains(ch)), Punctuation),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlight::{Highlighter, TokenKind};
+
+    /// Get the tokens of source code, without whitespace
+    fn tokens(source: &str) -> Vec<(TokenKind, &str)> {
+        Grammar::json()
+            .tokens(source)
+            .into_iter()
+            .filter(|(kind, _)| *kind != Plain)
+            .collect()
+    }
+
+    #[test]
+    fn json_works() {
+        assert_eq!(
+            tokens(r#"{"a": [1, -2.5e3, "b\"c"], "d" : {"e": null}, "f": true}"#),
+            [
+                (Punctuation, "{"),
+                (Property, r#""a""#),
+                (Punctuation, ":"),
+                (Punctuation, "["),
+                (Number, "1"),
+                (Punctuation, ","),
+                (Number, "-2.5e3"),
+                (Punctuation, ","),
+                (String, r#""b\"c""#),
+                (Punctuation, "],"),
+                (Property, r#""d""#),
+                (Punctuation, ":"),
+                (Punctuation, "{"),
+                (Property, r#""e""#),
+                (Punctuation, ":"),
+                (Constant, "null"),
+                (Punctuation, "},"),
+                (Property, r#""f""#),
+                (Punctuation, ":"),
+                (Constant, "true"),
+                (Punctuation, "}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn json_works_w_invalid_json() {
+        let source = "{\"a\": nope, 'b' \"open";
+        let joined: std::string::String = Grammar::json()
+            .tokens(source)
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect();
+        assert_eq!(joined, source);
+
+        assert_eq!(tokens("\"open").last(), Some(&(String, "\"open")));
+    }
+}
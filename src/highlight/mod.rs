@@ -0,0 +1,175 @@
+//! Syntax highlighting for code snippets
+//!
+//! Requires the `highlight` feature.
+//!
+//! Rust is highlighted with a lexer. Other languages use a `Grammar` of rules, with built-in
+//! grammars for JSON and TOML.
+//!
+//! # Examples
+//!
+//! ```
+//! use stilo::highlight::{Grammar, Highlighter, Rust, Theme};
+//!
+//! let theme = Theme::default();
+//! println!("{}", Rust.highlight("fn main() {}", &theme));
+//! println!("{}", Grammar::json().highlight(r#"{"a": [1, true]}"#, &theme));
+//! ```
+
+mod grammar;
+mod json;
+mod rust;
+mod toml;
+
+pub use grammar::{Grammar, Pattern, Rule};
+pub use rust::Rust;
+
+use crate::{Color::*, Style, StyledString};
+
+/// Syntax highlighter for a language
+pub trait Highlighter {
+    /// Split source code into tokens
+    ///
+    /// All text is included, so the tokens join to the source code
+    fn tokens<'a>(&self, source: &'a str) -> Vec<(TokenKind, &'a str)>;
+
+    /// Highlight source code with a `Theme`
+    fn highlight(&self, source: &str, theme: &Theme) -> StyledString {
+        self.tokens(source)
+            .into_iter()
+            .map(|(kind, text)| (text, theme.style(kind)))
+            .collect()
+    }
+}
+
+/// Kind of token, used to choose a `Style` from a `Theme`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// Whitespace, and other text with no style
+    Plain,
+    /// Keyword, such as `fn` or `let`
+    Keyword,
+    /// Name of a variable or field
+    Identifier,
+    /// Name of a type, such as `String` or `u8`
+    Type,
+    /// Name of a called function
+    Function,
+    /// Name of a macro, such as `println!`
+    Macro,
+    /// Lifetime, such as `'a`
+    Lifetime,
+    /// String or character literal
+    String,
+    /// Number literal
+    Number,
+    /// Constant, such as `true` or `MAX_LEN`
+    Constant,
+    /// Comment
+    Comment,
+    /// Attribute, such as `#[derive(Debug)]`
+    Attribute,
+    /// Key of a property, such as in a JSON object
+    Property,
+    /// Section header, such as a TOML table
+    Section,
+    /// Operator, such as `+` or `&`
+    Operator,
+    /// Punctuation, such as brackets and `;`
+    Punctuation,
+}
+
+/// Styles for each `TokenKind`
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{style, highlight::{Highlighter, Rust, Theme}};
+/// let theme = Theme {
+///     keyword: style!(Magenta + b),
+///     ..Default::default()
+/// };
+///
+/// println!("{}", Rust.highlight("let x = 1;", &theme));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// Whitespace, and other text with no style
+    pub plain: Style,
+    /// Keyword, such as `fn` or `let`
+    pub keyword: Style,
+    /// Name of a variable or field
+    pub identifier: Style,
+    /// Name of a type, such as `String` or `u8`
+    pub type_name: Style,
+    /// Name of a called function
+    pub function: Style,
+    /// Name of a macro, such as `println!`
+    pub macro_name: Style,
+    /// Lifetime, such as `'a`
+    pub lifetime: Style,
+    /// String or character literal
+    pub string: Style,
+    /// Number literal
+    pub number: Style,
+    /// Constant, such as `true` or `MAX_LEN`
+    pub constant: Style,
+    /// Comment
+    pub comment: Style,
+    /// Attribute, such as `#[derive(Debug)]`
+    pub attribute: Style,
+    /// Key of a property, such as in a JSON object
+    pub property: Style,
+    /// Section header, such as a TOML table
+    pub section: Style,
+    /// Operator, such as `+` or `&`
+    pub operator: Style,
+    /// Punctuation, such as brackets and `;`
+    pub punctuation: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            plain: Style::new(),
+            keyword: Style::new().color(Red),
+            identifier: Style::new(),
+            type_name: Style::new().color(Yellow),
+            function: Style::new().color(Blue),
+            macro_name: Style::new().color(Blue).bold(),
+            lifetime: Style::new().color(Cyan),
+            string: Style::new().color(Green),
+            number: Style::new().color(Magenta),
+            constant: Style::new().color(Magenta),
+            comment: Style::new().dim().italic(),
+            attribute: Style::new().color(Blue),
+            property: Style::new().color(Blue),
+            section: Style::new().color(Yellow).bold(),
+            operator: Style::new().color(Magenta),
+            punctuation: Style::new().color(Cyan),
+        }
+    }
+}
+
+impl Theme {
+    /// Get the `Style` for a `TokenKind`
+    pub fn style(&self, kind: TokenKind) -> Style {
+        match kind {
+            TokenKind::Plain => self.plain,
+            TokenKind::Keyword => self.keyword,
+            TokenKind::Identifier => self.identifier,
+            TokenKind::Type => self.type_name,
+            TokenKind::Function => self.function,
+            TokenKind::Macro => self.macro_name,
+            TokenKind::Lifetime => self.lifetime,
+            TokenKind::String => self.string,
+            TokenKind::Number => self.number,
+            TokenKind::Constant => self.constant,
+            TokenKind::Comment => self.comment,
+            TokenKind::Attribute => self.attribute,
+            TokenKind::Property => self.property,
+            TokenKind::Section => self.section,
+            TokenKind::Operator => self.operator,
+            TokenKind::Punctuation => self.punctuation,
+        }
+    }
+}
@@ -0,0 +1,527 @@
+use super::{Highlighter, TokenKind};
+
+/// Keywords, including reserved keywords
+const KEYWORDS: [&str; 49] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "type", "unsafe", "use",
+    "where", "while", "abstract", "become", "box", "do", "final", "gen", "macro", "override",
+    "priv", "try", "typeof", "unsized", "yield",
+];
+
+/// Primitive types
+const PRIMITIVES: [&str; 17] = [
+    "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64",
+    "i128", "isize", "f32", "f64",
+];
+
+/// Characters of operators, which are not punctuation
+const OPERATORS: &str = "+-*/%=!<>&|^?~@$";
+
+/// Rust syntax highlighter
+///
+/// Uses a lexer, so does not check if code is valid.
+/// Invalid code is still highlighted, as well as possible
+///
+/// # Examples
+///
+/// ```
+/// use stilo::highlight::{Highlighter, Rust, Theme, TokenKind};
+///
+/// let source = "let x: u8 = 1; // One";
+/// println!("{}", Rust.highlight(source, &Theme::default()));
+///
+/// let tokens = Rust.tokens(source);
+/// assert_eq!(tokens[0], (TokenKind::Keyword, "let"));
+/// assert_eq!(tokens.last(), Some(&(TokenKind::Comment, "// One")));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Rust;
+
+impl Highlighter for Rust {
+    fn tokens<'a>(&self, source: &'a str) -> Vec<(TokenKind, &'a str)> {
+        let mut lexer = Lexer {
+            source,
+            pos: 0,
+            previous: "",
+        };
+        let mut tokens = Vec::new();
+        while let Some(token) = lexer.next_token() {
+            tokens.push(token);
+        }
+        tokens
+    }
+}
+
+/// Lexer for Rust source code, with the current byte position
+struct Lexer<'a> {
+    source: &'a str,
+    pos: usize,
+    /// Text of the previous token, which is not whitespace or a comment
+    previous: &'a str,
+}
+
+impl<'a> Lexer<'a> {
+    /// Get the remaining source code
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    /// Get the character after skipping some characters, without advancing
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.peek_nth(0)
+    }
+
+    /// Advance past the next character
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.pos += ch.len_utf8();
+        Some(ch)
+    }
+
+    /// Advance while characters match
+    fn eat_while(&mut self, predicate: impl Fn(char) -> bool) {
+        while self.peek().is_some_and(&predicate) {
+            self.bump();
+        }
+    }
+
+    /// Advance past text, if the remaining source code starts with it
+    fn eat(&mut self, text: &str) -> bool {
+        if self.rest().starts_with(text) {
+            self.pos += text.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next_token(&mut self) -> Option<(TokenKind, &'a str)> {
+        let start = self.pos;
+        let kind = self.next_kind()?;
+        let text = &self.source[start..self.pos];
+        if kind != TokenKind::Plain && kind != TokenKind::Comment {
+            self.previous = text;
+        }
+        Some((kind, text))
+    }
+
+    /// Advance past the next token, and get its kind
+    fn next_kind(&mut self) -> Option<TokenKind> {
+        let rest = self.rest();
+        let ch = self.peek()?;
+
+        if ch.is_whitespace() {
+            self.eat_while(char::is_whitespace);
+            return Some(TokenKind::Plain);
+        }
+
+        if rest.starts_with("//") {
+            self.eat_while(|ch| ch != '\n');
+            return Some(TokenKind::Comment);
+        }
+        if rest.starts_with("/*") {
+            self.block_comment();
+            return Some(TokenKind::Comment);
+        }
+
+        if rest.starts_with("#[") || rest.starts_with("#![") {
+            self.attribute();
+            return Some(TokenKind::Attribute);
+        }
+
+        if self.raw_string() || self.string() {
+            return Some(TokenKind::String);
+        }
+
+        if ch == '\'' {
+            return Some(self.quote());
+        }
+        // Byte character, such as `b'x'`
+        if rest.starts_with("b'") {
+            self.bump();
+            self.quote();
+            return Some(TokenKind::String);
+        }
+
+        if ch.is_ascii_digit() {
+            self.number();
+            return Some(TokenKind::Number);
+        }
+
+        if is_ident_start(ch) {
+            return Some(self.identifier());
+        }
+
+        self.bump();
+        if OPERATORS.contains(ch) {
+            Some(TokenKind::Operator)
+        } else {
+            Some(TokenKind::Punctuation)
+        }
+    }
+
+    /// Block comment, which can be nested
+    fn block_comment(&mut self) {
+        let mut depth = 0;
+        while !self.rest().is_empty() {
+            if self.eat("/*") {
+                depth += 1;
+            } else if self.eat("*/") {
+                depth -= 1;
+                if depth == 0 {
+                    return;
+                }
+            } else {
+                self.bump();
+            }
+        }
+    }
+
+    /// Attribute, to the matching closing bracket
+    fn attribute(&mut self) {
+        self.eat("#");
+        self.eat("!");
+
+        let mut depth = 0;
+        while let Some(ch) = self.peek() {
+            if ch == '"' {
+                self.string();
+                continue;
+            }
+            self.bump();
+            match ch {
+                '[' => depth += 1,
+                ']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return;
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Raw string, such as `r#"text"#` or `br"text"`
+    ///
+    /// Returns `false` and does not advance if there is no raw string
+    fn raw_string(&mut self) -> bool {
+        let rest = self.rest();
+        let Some(after_prefix) = ["r", "br", "cr"]
+            .into_iter()
+            .find_map(|prefix| rest.strip_prefix(prefix))
+        else {
+            return false;
+        };
+
+        let hashes = after_prefix.len() - after_prefix.trim_start_matches('#').len();
+        if !after_prefix[hashes..].starts_with('"') {
+            return false;
+        }
+
+        let close = format!("\"{}", "#".repeat(hashes));
+        let body = &after_prefix[hashes + 1..];
+        let end = body
+            .find(&close)
+            .map_or(body.len(), |end| end + close.len());
+        self.pos += rest.len() - body.len() + end;
+        true
+    }
+
+    /// String, such as `"text"` or `b"text"`, with escapes
+    ///
+    /// Returns `false` and does not advance if there is no string
+    fn string(&mut self) -> bool {
+        let start = self.pos;
+        self.eat("b");
+        self.eat("c");
+        if !self.eat("\"") {
+            self.pos = start;
+            return false;
+        }
+
+        while let Some(ch) = self.bump() {
+            match ch {
+                '\\' => {
+                    self.bump();
+                }
+                '"' => break,
+                _ => (),
+            }
+        }
+        true
+    }
+
+    /// Character literal or lifetime, starting with `'`
+    fn quote(&mut self) -> TokenKind {
+        self.bump();
+
+        match (self.peek(), self.peek_nth(1)) {
+            // Escaped character, such as `'\n'` or `'\u{1F600}'`
+            (Some('\\'), _) => {
+                self.bump();
+                self.bump();
+                self.eat_while(|ch| ch != '\'' && ch != '\n');
+                self.eat("'");
+                TokenKind::String
+            }
+            (Some(_), Some('\'')) => {
+                self.bump();
+                self.bump();
+                TokenKind::String
+            }
+            (Some(ch), _) if is_ident_start(ch) => {
+                self.eat_while(is_ident_continue);
+                TokenKind::Lifetime
+            }
+            _ => TokenKind::Punctuation,
+        }
+    }
+
+    /// Number, with a base prefix, decimal point, exponent, and suffix
+    fn number(&mut self) {
+        let hex = self.rest().starts_with("0x");
+        loop {
+            self.eat_while(is_ident_continue);
+
+            let last = self.source[..self.pos].chars().last();
+            match (self.peek(), self.peek_nth(1)) {
+                // Decimal point, but not a range or method call
+                (Some('.'), Some(next)) if next.is_ascii_digit() => {
+                    self.bump();
+                }
+                // Exponent sign
+                (Some('+' | '-'), Some(next))
+                    if !hex && matches!(last, Some('e' | 'E')) && next.is_ascii_digit() =>
+                {
+                    self.bump();
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Identifier, keyword, or macro name
+    fn identifier(&mut self) -> TokenKind {
+        let start = self.pos;
+        if self.rest().starts_with("r#") && self.peek_nth(2).is_some_and(is_ident_start) {
+            self.pos += 2;
+        }
+        self.eat_while(is_ident_continue);
+        let name = &self.source[start..self.pos];
+
+        if self.rest().starts_with('!') && !self.rest().starts_with("!=") {
+            self.bump();
+            return TokenKind::Macro;
+        }
+
+        if KEYWORDS.contains(&name) {
+            return TokenKind::Keyword;
+        }
+        if name == "true" || name == "false" {
+            return TokenKind::Constant;
+        }
+        if PRIMITIVES.contains(&name) {
+            return TokenKind::Type;
+        }
+
+        if name.starts_with(|ch: char| ch.is_uppercase()) {
+            let is_constant = name.len() > 1
+                && name
+                    .chars()
+                    .all(|ch| ch.is_uppercase() || ch.is_ascii_digit() || ch == '_');
+            return if is_constant {
+                TokenKind::Constant
+            } else {
+                TokenKind::Type
+            };
+        }
+
+        if self.previous == "fn" || self.rest().starts_with('(') || self.rest().starts_with("::<") {
+            return TokenKind::Function;
+        }
+        TokenKind::Identifier
+    }
+}
+
+fn is_ident_start(ch: char) -> bool {
+    ch == '_' || ch.is_alphabetic()
+}
+
+fn is_ident_continue(ch: char) -> bool {
+    ch == '_' || ch.is_alphanumeric()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlight::Theme;
+    use TokenKind::*;
+
+    /// Get the tokens of source code, without whitespace
+    fn tokens(source: &str) -> Vec<(TokenKind, &str)> {
+        Rust.tokens(source)
+            .into_iter()
+            .filter(|(kind, _)| *kind != Plain)
+            .collect()
+    }
+
+    #[test]
+    fn tokens_join_to_source() {
+        let source = include_str!("rust.rs");
+        let joined: std::string::String = Rust
+            .tokens(source)
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect();
+        assert_eq!(joined, source);
+    }
+
+    #[test]
+    fn rust_tokens_work() {
+        assert_eq!(
+            tokens("pub fn add(a: u8, b: Wrapper) -> Self {}"),
+            [
+                (Keyword, "pub"),
+                (Keyword, "fn"),
+                (Function, "add"),
+                (Punctuation, "("),
+                (Identifier, "a"),
+                (Punctuation, ":"),
+                (Type, "u8"),
+                (Punctuation, ","),
+                (Identifier, "b"),
+                (Punctuation, ":"),
+                (Type, "Wrapper"),
+                (Punctuation, ")"),
+                (Operator, "-"),
+                (Operator, ">"),
+                (Keyword, "Self"),
+                (Punctuation, "{"),
+                (Punctuation, "}"),
+            ]
+        );
+        assert_eq!(
+            tokens("x != MAX_LEN && r#type"),
+            [
+                (Identifier, "x"),
+                (Operator, "!"),
+                (Operator, "="),
+                (Constant, "MAX_LEN"),
+                (Operator, "&"),
+                (Operator, "&"),
+                (Identifier, "r#type"),
+            ]
+        );
+        assert_eq!(
+            tokens("println!(\"{}\", true); vec![]"),
+            [
+                (Macro, "println!"),
+                (Punctuation, "("),
+                (String, "\"{}\""),
+                (Punctuation, ","),
+                (Constant, "true"),
+                (Punctuation, ")"),
+                (Punctuation, ";"),
+                (Macro, "vec!"),
+                (Punctuation, "["),
+                (Punctuation, "]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rust_tokens_work_w_literals() {
+        assert_eq!(
+            tokens(r###""a \" b" b"x" r"c\" r##"d "# e"## 'x' '\n' b'\'' '\u{1F600}'"###),
+            [
+                (String, r#""a \" b""#),
+                (String, r#"b"x""#),
+                (String, r#"r"c\""#),
+                (String, r###"r##"d "# e"##"###),
+                (String, "'x'"),
+                (String, r"'\n'"),
+                (String, r"b'\''"),
+                (String, r"'\u{1F600}'"),
+            ]
+        );
+        assert_eq!(
+            tokens("1 1.5 0xFF_u8 1e-3 2.5E+10f64 1..2 x.0"),
+            [
+                (Number, "1"),
+                (Number, "1.5"),
+                (Number, "0xFF_u8"),
+                (Number, "1e-3"),
+                (Number, "2.5E+10f64"),
+                (Number, "1"),
+                (Punctuation, "."),
+                (Punctuation, "."),
+                (Number, "2"),
+                (Identifier, "x"),
+                (Punctuation, "."),
+                (Number, "0"),
+            ]
+        );
+        assert_eq!(
+            tokens("fn f<'a>(x: &'a str) -> 'static"),
+            [
+                (Keyword, "fn"),
+                (Function, "f"),
+                (Operator, "<"),
+                (Lifetime, "'a"),
+                (Operator, ">"),
+                (Punctuation, "("),
+                (Identifier, "x"),
+                (Punctuation, ":"),
+                (Operator, "&"),
+                (Lifetime, "'a"),
+                (Type, "str"),
+                (Punctuation, ")"),
+                (Operator, "-"),
+                (Operator, ">"),
+                (Lifetime, "'static"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rust_tokens_work_w_comments_and_attributes() {
+        assert_eq!(
+            tokens("#[derive(Debug)] #![doc = \"]\"] x // a\n/* b /* c */ d */ y"),
+            [
+                (Attribute, "#[derive(Debug)]"),
+                (Attribute, "#![doc = \"]\"]"),
+                (Identifier, "x"),
+                (Comment, "// a"),
+                (Comment, "/* b /* c */ d */"),
+                (Identifier, "y"),
+            ]
+        );
+        assert_eq!(tokens("/* open \"x"), [(Comment, "/* open \"x")]);
+        assert_eq!(tokens("\"open"), [(String, "\"open")]);
+    }
+
+    #[test]
+    fn rust_highlight_works() {
+        let theme = Theme::default();
+        let text = Rust.highlight("let x = 1;", &theme);
+
+        assert_eq!(text.plain(), "let x = 1;");
+        assert_eq!(text.spans()[0].style, theme.keyword);
+        assert_eq!(
+            text.to_string(),
+            format!(
+                "{} x {} {}{}",
+                theme.keyword.format("let"),
+                theme.operator.format("="),
+                theme.number.format("1"),
+                theme.punctuation.format(";"),
+            )
+        );
+    }
+}
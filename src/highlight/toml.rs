@@ -0,0 +1,232 @@
+use super::{Grammar, Pattern, Rule, TokenKind::*};
+
+/// Check if a character can be in a bare key
+fn is_bare_key(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '-' || ch == '_'
+}
+
+/// Rules for values, in any state
+fn value_rules() -> [Rule; 12] {
+    [
+        Rule::new(Pattern::Line("#"), Comment),
+        Rule::new(
+            Pattern::Delimited {
+                open: "\"\"\"",
+                close: "\"\"\"",
+                escape: Some('\\'),
+            },
+            String,
+        ),
+        Rule::new(
+            Pattern::Delimited {
+                open: "'''",
+                close: "'''",
+                escape: None,
+            },
+            String,
+        ),
+        Rule::new(
+            Pattern::Delimited {
+                open: "\"",
+                close: "\"",
+                escape: Some('\\'),
+            },
+            String,
+        ),
+        Rule::new(
+            Pattern::Delimited {
+                open: "'",
+                close: "'",
+                escape: None,
+            },
+            String,
+        ),
+        Rule::new(Pattern::Words(&["true", "false"]), Constant),
+        Rule::new(
+            Pattern::Words(&["inf", "nan", "+inf", "+nan", "-inf", "-nan"]),
+            Number,
+        ),
+        // Numbers, dates, and times
+        Rule::new(
+            Pattern::Word(
+                |ch| ch.is_ascii_digit(),
+                |ch| ch.is_ascii_alphanumeric() || "_.:-+".contains(ch),
+            ),
+            Number,
+        ),
+        Rule::new(Pattern::Number, Number),
+        Rule::new(Pattern::Text("["), Punctuation).push("array"),
+        Rule::new(Pattern::Text("{"), Punctuation).push("inline table"),
+        Rule::new(Pattern::Text(","), Punctuation),
+    ]
+}
+
+impl Grammar {
+    /// Grammar for TOML
+    ///
+    /// Keys are `TokenKind::Property`, and table headers are `TokenKind::Section`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stilo::highlight::{Grammar, Highlighter, Theme};
+    /// let toml = "[package]\nname = \"stilo\"\nkeywords = [\"color\", \"ansi\"]\n";
+    /// println!("{}", Grammar::toml().highlight(toml, &Theme::default()));
+    /// ```
+    pub fn toml() -> Self {
+        let quoted_key = |open| Pattern::Delimited {
+            open,
+            close: open,
+            escape: (open == "\"").then_some('\\'),
+        };
+
+        let key_rules = [
+            Rule::new(Pattern::Chars(is_bare_key), Property).before("="),
+            Rule::new(Pattern::Chars(is_bare_key), Property).before("."),
+            Rule::new(quoted_key("\""), Property).before("="),
+            Rule::new(quoted_key("\""), Property).before("."),
+            Rule::new(quoted_key("'"), Property).before("="),
+            Rule::new(quoted_key("'"), Property).before("."),
+            Rule::new(Pattern::Text("."), Punctuation),
+        ];
+
+        Grammar::new()
+            .state(
+                "key",
+                [
+                    Rule::new(Pattern::Line("#"), Comment),
+                    Rule::new(
+                        Pattern::Delimited {
+                            open: "[[",
+                            close: "]]",
+                            escape: None,
+                        },
+                        Section,
+                    ),
+                    Rule::new(
+                        Pattern::Delimited {
+                            open: "[",
+                            close: "]",
+                            escape: None,
+                        },
+                        Section,
+                    ),
+                ]
+                .into_iter()
+                .chain(key_rules)
+                .chain([Rule::new(Pattern::Text("="), Operator).push("value")]),
+            )
+            .state(
+                "value",
+                [Rule::new(Pattern::Text("\n"), Plain).pop()]
+                    .into_iter()
+                    .chain(value_rules()),
+            )
+            .state(
+                "array",
+                [Rule::new(Pattern::Text("]"), Punctuation).pop()]
+                    .into_iter()
+                    .chain(value_rules()),
+            )
+            .state(
+                "inline table",
+                [
+                    Rule::new(Pattern::Text("}"), Punctuation).pop(),
+                    Rule::new(Pattern::Text("="), Operator),
+                ]
+                .into_iter()
+                .chain(key_rules)
+                .chain(value_rules()),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlight::{Highlighter, TokenKind};
+
+    /// Get the tokens of source code, without whitespace
+    fn tokens(source: &str) -> Vec<(TokenKind, &str)> {
+        Grammar::toml()
+            .tokens(source)
+            .into_iter()
+            .filter(|(kind, text)| *kind != Plain || !text.trim().is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn toml_works() {
+        let source = r#"
+# Comment
+[package]
+name = "stilo" # Name
+site."docs.rs" = 'x'
+version = 1.5e3
+
+[[bin]]
+path = """a
+b"""
+"#;
+
+        assert_eq!(
+            tokens(source),
+            [
+                (Comment, "# Comment"),
+                (Section, "[package]"),
+                (Property, "name"),
+                (Operator, "="),
+                (String, r#""stilo""#),
+                (Comment, "# Name"),
+                (Property, "site"),
+                (Punctuation, "."),
+                (Property, r#""docs.rs""#),
+                (Operator, "="),
+                (String, "'x'"),
+                (Property, "version"),
+                (Operator, "="),
+                (Number, "1.5e3"),
+                (Section, "[[bin]]"),
+                (Property, "path"),
+                (Operator, "="),
+                (String, "\"\"\"a\nb\"\"\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn toml_works_w_arrays_and_tables() {
+        assert_eq!(
+            tokens("a = [\n  1, # One\n  [true],\n]\nb = { c = 1979-05-27T07:32:00Z, d.e = -inf }\nf = 2"),
+            [
+                (Property, "a"),
+                (Operator, "="),
+                (Punctuation, "["),
+                (Number, "1"),
+                (Punctuation, ","),
+                (Comment, "# One"),
+                (Punctuation, "["),
+                (Constant, "true"),
+                (Punctuation, "]"),
+                (Punctuation, ","),
+                (Punctuation, "]"),
+                (Property, "b"),
+                (Operator, "="),
+                (Punctuation, "{"),
+                (Property, "c"),
+                (Operator, "="),
+                (Number, "1979-05-27T07:32:00Z"),
+                (Punctuation, ","),
+                (Property, "d"),
+                (Punctuation, "."),
+                (Property, "e"),
+                (Operator, "="),
+                (Number, "-inf"),
+                (Punctuation, "}"),
+                (Property, "f"),
+                (Operator, "="),
+                (Number, "2"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,491 @@
+use std::{error::Error, fmt};
+
+use crate::{Color::*, Style, StyledString};
+
+/// Nested arrays and objects allowed, before returning an error
+const MAX_DEPTH: usize = 128;
+
+/// Error from formatting invalid JSON, with the byte offset in the text
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonError {
+    /// Byte offset of the error
+    pub offset: usize,
+    /// Kind of error
+    pub kind: JsonErrorKind,
+}
+
+/// Kind of `JsonError`
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonErrorKind {
+    /// Text ended before the value was complete
+    UnexpectedEnd,
+    /// Character is not valid here
+    UnexpectedCharacter(char),
+    /// Number is not valid, such as `01` or `1.`
+    InvalidNumber,
+    /// Escape in a string is not valid, such as `\x`
+    InvalidEscape,
+    /// Arrays and objects are nested too deeply
+    TooDeep,
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            JsonErrorKind::UnexpectedEnd => write!(f, "unexpected end of JSON")?,
+            JsonErrorKind::UnexpectedCharacter(ch) => write!(f, "unexpected character {ch:?}")?,
+            JsonErrorKind::InvalidNumber => write!(f, "invalid number")?,
+            JsonErrorKind::InvalidEscape => write!(f, "invalid escape in string")?,
+            JsonErrorKind::TooDeep => write!(f, "arrays and objects are nested too deeply")?,
+        }
+        write!(f, " at offset {}", self.offset)
+    }
+}
+
+impl Error for JsonError {}
+
+/// Validate and pretty-print JSON, with a `Style` for each kind of value
+///
+/// Strings and numbers are written as in the input, including escapes.
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{style, JsonFormatter};
+/// let formatter = JsonFormatter {
+///     indent: "    ".into(),
+///     key: style!(Cyan),
+///     ..Default::default()
+/// };
+///
+/// let text = formatter.format(r#"{"name":"stilo","tags":["color"],"stable":false}"#).unwrap();
+/// println!("{}", text);
+/// assert_eq!(
+///     text.plain(),
+///     "{\n    \"name\": \"stilo\",\n    \"tags\": [\n        \"color\"\n    ],\n    \"stable\": false\n}"
+/// );
+///
+/// let error = formatter.format("[1, 2").unwrap_err();
+/// assert_eq!(error.offset, 5);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonFormatter {
+    /// Text for each level of indentation
+    pub indent: String,
+    /// Keys of objects
+    pub key: Style,
+    /// String values
+    pub string: Style,
+    /// Numbers
+    pub number: Style,
+    /// `true` and `false`
+    pub boolean: Style,
+    /// `null`
+    pub null: Style,
+    /// Brackets, braces, commas, and colons
+    pub punctuation: Style,
+}
+
+impl Default for JsonFormatter {
+    fn default() -> Self {
+        Self {
+            indent: "  ".into(),
+            key: Style::new().color(Blue).bold(),
+            string: Style::new().color(Green),
+            number: Style::new().color(Cyan),
+            boolean: Style::new().color(Yellow),
+            null: Style::new().dim(),
+            punctuation: Style::new(),
+        }
+    }
+}
+
+impl JsonFormatter {
+    /// Create a `JsonFormatter` with the default indent and styles
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validate and pretty-print JSON
+    pub fn format(&self, json: &str) -> Result<StyledString, JsonError> {
+        let mut parser = Parser {
+            source: json,
+            pos: 0,
+            formatter: self,
+            output: StyledString::new(),
+        };
+
+        parser.skip_whitespace();
+        parser.value(0)?;
+        parser.skip_whitespace();
+        if let Some(ch) = parser.peek() {
+            return Err(parser.error(JsonErrorKind::UnexpectedCharacter(ch)));
+        }
+
+        Ok(parser.output)
+    }
+}
+
+/// Parser for JSON, which writes formatted text while parsing
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+    formatter: &'a JsonFormatter,
+    output: StyledString,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.source[self.pos..].chars().next()
+    }
+
+    fn error(&self, kind: JsonErrorKind) -> JsonError {
+        JsonError {
+            offset: self.pos,
+            kind,
+        }
+    }
+
+    /// Get an error for the next character, or the end of the text
+    fn unexpected(&self) -> JsonError {
+        match self.peek() {
+            Some(ch) => self.error(JsonErrorKind::UnexpectedCharacter(ch)),
+            None => self.error(JsonErrorKind::UnexpectedEnd),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.source[self.pos..];
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t', '\n', '\r']).len();
+    }
+
+    /// Advance past a character, if it is next
+    fn eat(&mut self, ch: char) -> bool {
+        if self.peek() == Some(ch) {
+            self.pos += ch.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Write text from the source, from a start position to the current position
+    fn push_from(&mut self, start: usize, style: Style) {
+        self.output.push(&self.source[start..self.pos], style);
+    }
+
+    fn punctuation(&mut self, text: &str) {
+        self.output.push(text, self.formatter.punctuation);
+    }
+
+    /// Write a new line, and indentation for a depth
+    fn new_line(&mut self, depth: usize) {
+        self.output
+            .push_plain(format!("\n{}", self.formatter.indent.repeat(depth)));
+    }
+
+    fn value(&mut self, depth: usize) -> Result<(), JsonError> {
+        match self.peek() {
+            Some('{') => self.object(depth),
+            Some('[') => self.array(depth),
+            Some('"') => {
+                let start = self.pos;
+                self.string()?;
+                self.push_from(start, self.formatter.string);
+                Ok(())
+            }
+            Some('-' | '0'..='9') => self.number(),
+            Some(_) => {
+                for (word, style) in [
+                    ("true", self.formatter.boolean),
+                    ("false", self.formatter.boolean),
+                    ("null", self.formatter.null),
+                ] {
+                    if self.source[self.pos..].starts_with(word) {
+                        self.output.push(word, style);
+                        self.pos += word.len();
+                        return Ok(());
+                    }
+                }
+                Err(self.unexpected())
+            }
+            None => Err(self.unexpected()),
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<(), JsonError> {
+        if depth >= MAX_DEPTH {
+            return Err(self.error(JsonErrorKind::TooDeep));
+        }
+        self.eat('{');
+        self.skip_whitespace();
+        if self.eat('}') {
+            self.punctuation("{}");
+            return Ok(());
+        }
+        self.punctuation("{");
+
+        loop {
+            self.new_line(depth + 1);
+
+            if self.peek() != Some('"') {
+                return Err(self.unexpected());
+            }
+            let start = self.pos;
+            self.string()?;
+            self.push_from(start, self.formatter.key);
+
+            self.skip_whitespace();
+            if !self.eat(':') {
+                return Err(self.unexpected());
+            }
+            self.punctuation(":");
+            self.output.push_plain(" ");
+            self.skip_whitespace();
+            self.value(depth + 1)?;
+            self.skip_whitespace();
+
+            if self.eat(',') {
+                self.punctuation(",");
+                self.skip_whitespace();
+            } else if self.eat('}') {
+                self.new_line(depth);
+                self.punctuation("}");
+                return Ok(());
+            } else {
+                return Err(self.unexpected());
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<(), JsonError> {
+        if depth >= MAX_DEPTH {
+            return Err(self.error(JsonErrorKind::TooDeep));
+        }
+        self.eat('[');
+        self.skip_whitespace();
+        if self.eat(']') {
+            self.punctuation("[]");
+            return Ok(());
+        }
+        self.punctuation("[");
+
+        loop {
+            self.new_line(depth + 1);
+            self.value(depth + 1)?;
+            self.skip_whitespace();
+
+            if self.eat(',') {
+                self.punctuation(",");
+                self.skip_whitespace();
+            } else if self.eat(']') {
+                self.new_line(depth);
+                self.punctuation("]");
+                return Ok(());
+            } else {
+                return Err(self.unexpected());
+            }
+        }
+    }
+
+    /// Validate a string, without writing it
+    fn string(&mut self) -> Result<(), JsonError> {
+        self.eat('"');
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some('\\') => {
+                    let start = self.pos;
+                    self.pos += 1;
+                    let valid = match self.peek() {
+                        Some('"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't') => {
+                            self.pos += 1;
+                            true
+                        }
+                        Some('u') => {
+                            self.pos += 1;
+                            let hex = self.source[self.pos..].get(..4);
+                            let valid =
+                                hex.is_some_and(|hex| hex.chars().all(|ch| ch.is_ascii_hexdigit()));
+                            if valid {
+                                self.pos += 4;
+                            }
+                            valid
+                        }
+                        _ => false,
+                    };
+                    if !valid {
+                        self.pos = start;
+                        return Err(self.error(JsonErrorKind::InvalidEscape));
+                    }
+                }
+                Some(ch) if (ch as u32) < 0x20 => {
+                    return Err(self.unexpected());
+                }
+                Some(ch) => self.pos += ch.len_utf8(),
+                None => return Err(self.unexpected()),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<(), JsonError> {
+        let start = self.pos;
+        let digits = |parser: &mut Self| {
+            let rest = &parser.source[parser.pos..];
+            let len = rest.len()
+                - rest
+                    .trim_start_matches(|ch: char| ch.is_ascii_digit())
+                    .len();
+            parser.pos += len;
+            len
+        };
+        let invalid = |parser: &mut Self| {
+            parser.pos = start;
+            Err(parser.error(JsonErrorKind::InvalidNumber))
+        };
+
+        self.eat('-');
+        let integer_start = self.pos;
+        let integer = digits(self);
+        if integer == 0 || (integer > 1 && self.source[integer_start..].starts_with('0')) {
+            return invalid(self);
+        }
+        if self.eat('.') && digits(self) == 0 {
+            return invalid(self);
+        }
+        if self.eat('e') || self.eat('E') {
+            let _ = self.eat('+') || self.eat('-');
+            if digits(self) == 0 {
+                return invalid(self);
+            }
+        }
+
+        self.push_from(start, self.formatter.number);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style;
+
+    #[test]
+    fn json_formatter_works() {
+        let format = |json| JsonFormatter::new().format(json).unwrap().plain();
+
+        assert_eq!(format("1"), "1");
+        assert_eq!(format("  -0.5e+10 "), "-0.5e+10");
+        assert_eq!(format("\"a\\n\\u00e9 é\""), "\"a\\n\\u00e9 é\"");
+        assert_eq!(format("[]"), "[]");
+        assert_eq!(format("{ }"), "{}");
+        assert_eq!(format("[1,true ,null]"), "[\n  1,\n  true,\n  null\n]");
+        assert_eq!(
+            format(r#"{"a":{"b":[false,{}]},"c" : "d"}"#),
+            concat!(
+                "{\n",
+                "  \"a\": {\n",
+                "    \"b\": [\n",
+                "      false,\n",
+                "      {}\n",
+                "    ]\n",
+                "  },\n",
+                "  \"c\": \"d\"\n",
+                "}",
+            )
+        );
+    }
+
+    #[test]
+    fn json_formatter_works_w_styles() {
+        let formatter = JsonFormatter {
+            indent: "\t".into(),
+            key: style!(Blue),
+            string: style!(Green),
+            number: style!(Cyan),
+            boolean: style!(Yellow),
+            null: style!(+d),
+            punctuation: style!(+b),
+        };
+
+        let text = formatter.format(r#"{"a": [1, "x", true, null]}"#).unwrap();
+        let expected: StyledString = [
+            ("{", style!(+b)),
+            ("\n\t", style!()),
+            ("\"a\"", style!(Blue)),
+            (":", style!(+b)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(text.spans()[..4], *expected.spans());
+        assert_eq!(
+            text.plain(),
+            "{\n\t\"a\": [\n\t\t1,\n\t\t\"x\",\n\t\ttrue,\n\t\tnull\n\t]\n}"
+        );
+        assert!(text
+            .spans()
+            .iter()
+            .any(|span| span.text == "1" && span.style == style!(Cyan)));
+        assert!(text
+            .spans()
+            .iter()
+            .any(|span| span.text == "null" && span.style == style!(+d)));
+    }
+
+    #[test]
+    fn json_formatter_errors() {
+        let error = |json: &str| JsonFormatter::new().format(json).unwrap_err();
+
+        assert_eq!(
+            error("[1, 2"),
+            JsonError {
+                offset: 5,
+                kind: JsonErrorKind::UnexpectedEnd
+            }
+        );
+        assert_eq!(
+            error("[1 2]"),
+            JsonError {
+                offset: 3,
+                kind: JsonErrorKind::UnexpectedCharacter('2')
+            }
+        );
+        assert_eq!(error("{\"a\" 1}").offset, 5);
+        assert_eq!(
+            error("{a: 1}").kind,
+            JsonErrorKind::UnexpectedCharacter('a')
+        );
+        assert_eq!(error("[1,]").kind, JsonErrorKind::UnexpectedCharacter(']'));
+        assert_eq!(error("1 2").offset, 2);
+        assert_eq!(error("").kind, JsonErrorKind::UnexpectedEnd);
+        assert_eq!(error("nul").kind, JsonErrorKind::UnexpectedCharacter('n'));
+
+        assert_eq!(error("[01]").kind, JsonErrorKind::InvalidNumber);
+        assert_eq!(error("[01]").offset, 1);
+        assert_eq!(error("1.").kind, JsonErrorKind::InvalidNumber);
+        assert_eq!(error("-").kind, JsonErrorKind::InvalidNumber);
+        assert_eq!(error("1e+").kind, JsonErrorKind::InvalidNumber);
+
+        assert_eq!(
+            error(r#"["a\x"]"#),
+            JsonError {
+                offset: 3,
+                kind: JsonErrorKind::InvalidEscape
+            }
+        );
+        assert_eq!(error(r#""\u12""#).kind, JsonErrorKind::InvalidEscape);
+        assert_eq!(
+            error("\"a\nb\"").kind,
+            JsonErrorKind::UnexpectedCharacter('\n')
+        );
+
+        assert_eq!(error(&"[".repeat(200)).kind, JsonErrorKind::TooDeep);
+        assert_eq!(
+            error("[1, 2").to_string(),
+            "unexpected end of JSON at offset 5"
+        );
+    }
+}
@@ -2,9 +2,57 @@
 mod tests;
 
 mod wrappers;
+mod border;
+mod diagnostic;
+mod diff;
+mod grep_colors;
+#[cfg(feature = "highlight")]
+pub mod highlight;
+mod json;
+mod link;
+mod ls_colors;
+mod markup;
+mod matches;
+mod panel;
+mod parse;
+mod progress;
+#[cfg(feature = "serde")]
+mod serde_impl;
 mod style;
+mod styled_string;
+mod styled_writer;
 mod stylize;
 mod stylize_many;
+mod stylize_static;
+mod table;
+mod terminal;
+mod tree;
+
+pub use border::Border;
+pub use diagnostic::{Diagnostic, DiagnosticTheme, Label, Severity};
+pub use diff::{diff_lines, Change, DiffFormatter};
+pub use grep_colors::GrepColors;
+pub use json::{JsonError, JsonErrorKind, JsonFormatter};
+pub use link::{Link, LinkMode};
+pub use ls_colors::LsColors;
+pub use markup::{markup, parse_markup, parse_markup_with, MarkupError, MarkupErrorKind};
+pub use matches::MatchHighlighter;
+pub use panel::Panel;
+pub use parse::ParseStyleError;
+pub use progress::ProgressBar;
+pub use styled_string::{Span, StyledString};
+pub use styled_writer::StyledWriter;
+pub use table::{Alignment, Table};
+pub use terminal::{strip_ansi, ColorSupport, Stream};
+pub use tree::{Tree, TreeFormatter};
+#[doc(hidden)]
+pub use stilo_macros::split_segments;
+#[doc(hidden)]
+pub use stylize_static::{has_format_args, static_bytes, static_len, static_str};
+#[doc(hidden)]
+pub use terminal::text_for_stream;
+#[doc(hidden)]
+pub use wrappers::{FmtWriterRef, IoWriterRef};
 
 use Color::*;
 
@@ -17,15 +65,50 @@ macro_rules! color_enum {
         #[derive(Debug, Clone, Copy, PartialEq)]
         pub enum Color {
             $( $kind, )*
+            /// Color from the 256-color palette
+            Fixed(u8),
+            /// 24-bit color, as red, green, and blue
+            Rgb(u8, u8, u8),
         }
 
         impl Color {
-            /// Get the ANSI parameter for the `Color`
-            fn param(self) -> usize {
+            /// Add the ANSI parameters for the `Color`, as foreground or background
+            const fn push_params(self, params: Params, background: bool) -> Params {
+                let offset = if background { 10 } else { 0 };
+                match self {
+                    $( $kind => params.push($number + offset), )*
+                    Fixed(index) => params.push(38 + offset).push(5).push(index),
+                    Rgb(r, g, b) => params.push(38 + offset).push(2).push(r).push(g).push(b),
+                }
+            }
+
+            /// Get a named `Color` from its foreground ANSI parameter
+            fn from_param(param: u8) -> Option<Self> {
+                match param {
+                    $( $number => Some($kind), )*
+                    _ => None,
+                }
+            }
+
+            /// Get the name of the `Color`, as written in the macros
+            ///
+            /// Returns `None` for `Fixed` and `Rgb` colors
+            pub fn name(self) -> Option<&'static str> {
                 match self {
-                    $( $kind => $number , )*
+                    $( $kind => Some(stringify!($kind)), )*
+                    Fixed(_) | Rgb(..) => None,
                 }
             }
+
+            /// Get a `Color` from its name, ignoring case
+            pub fn from_name(name: &str) -> Option<Self> {
+                $(
+                    if name.eq_ignore_ascii_case(stringify!($kind)) {
+                        return Some($kind);
+                    }
+                )*
+                None
+            }
         }
     };
 }
@@ -43,9 +126,9 @@ color_enum!(
 
 /// Stylize text with ANSI codes
 ///
-/// Color uses `Color` enum
+/// Color and background use `Color` enum
 ///
-/// Decorations include `bold`, `dim`, `italic`, and `underline`
+/// Decorations include `bold`, `dim`, `italic`, `underline`, `blink`, `reverse`, and `strikethrough`
 ///
 /// Create with `Style::new()`
 /// 
@@ -63,19 +146,32 @@ pub struct Style {
     dim: bool,
     italic: bool,
     underline: bool,
+    blink: bool,
+    reverse: bool,
+    strikethrough: bool,
     color: Option<Color>,
+    background: Option<Color>,
 }
 
 /// Private macro for implementing decoration methods to `Style` struct
 macro_rules! decor_method {
     ( $short: ident $long: ident ) => {
         /// Add a decoration
-        pub fn $long(mut self) -> Self {
+        pub const fn $long(mut self) -> Self {
+            self.$long = true;
+            self
+        }
+        /// Add a decoration
+        pub const fn $short(mut self) -> Self {
             self.$long = true;
             self
         }
+    };
+
+    // No short method
+    ( $long: ident ) => {
         /// Add a decoration
-        pub fn $short(mut self) -> Self {
+        pub const fn $long(mut self) -> Self {
             self.$long = true;
             self
         }
@@ -84,31 +180,125 @@ macro_rules! decor_method {
 
 impl Style {
     /// Create an empty `Style` struct
-    pub fn new() -> Self {
-        Self::default()
+    ///
+    /// All builder methods are `const`, so styles can be declared as constants
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stilo::{style, Style, Color::*};
+    /// const ERROR: Style = Style::new().color(Red).bold();
+    /// const WARNING: Style = style!(Yellow + b);
+    /// ```
+    pub const fn new() -> Self {
+        Self {
+            bold: false,
+            dim: false,
+            italic: false,
+            underline: false,
+            blink: false,
+            reverse: false,
+            strikethrough: false,
+            color: None,
+            background: None,
+        }
     }
 
     /// Add a color
     ///
     /// Overrides any previous color
-    pub fn color(mut self, color: Color) -> Self {
+    pub const fn color(mut self, color: Color) -> Self {
         self.color = Some(color);
         self
     }
 
+    /// Add a background color
+    ///
+    /// Overrides any previous background color
+    pub const fn background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
     decor_method!(b bold);
     decor_method!(d dim);
     decor_method!(i italic);
     decor_method!(u underline);
+    decor_method!(blink);
+    decor_method!(r reverse);
+    decor_method!(s strikethrough);
+
+    /// Apply another `Style` on top of this one
+    ///
+    /// Colors of the other style override, and decorations are combined
+    pub const fn merge(self, other: Style) -> Self {
+        Self {
+            bold: self.bold || other.bold,
+            dim: self.dim || other.dim,
+            italic: self.italic || other.italic,
+            underline: self.underline || other.underline,
+            blink: self.blink || other.blink,
+            reverse: self.reverse || other.reverse,
+            strikethrough: self.strikethrough || other.strikethrough,
+            color: match other.color {
+                Some(color) => Some(color),
+                None => self.color,
+            },
+            background: match other.background {
+                Some(color) => Some(color),
+                None => self.background,
+            },
+        }
+    }
 
     /// Format text with `Style`
     ///
     /// Returns text as `String` if no color or decorations are given
     pub fn format(self, text: &str) -> String {
-        let mut params = Vec::new();
+        let params = self.params();
+
+        if params.is_empty() {
+            text.into()
+        } else {
+            format!("\x1b[{}m{text}\x1b[0m", params.as_str())
+        }
+    }
+
+    /// Get the escape code to start the `Style`, without text
+    ///
+    /// Empty if no color or decorations are given
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stilo::style;
+    /// let style = style!(Red + bold);
+    /// println!("{}Hello{}", style.prefix(), style.suffix());
+    /// ```
+    pub fn prefix(self) -> impl std::fmt::Display {
+        Prefix(self.params())
+    }
+
+    /// Get the escape code to reset after the `Style`
+    ///
+    /// Empty if no color or decorations are given
+    pub fn suffix(self) -> &'static str {
+        if self.params().is_empty() {
+            ""
+        } else {
+            "\x1b[0m"
+        }
+    }
+
+    /// Get the ANSI parameters for the `Style`, separated with `;`
+    const fn params(self) -> Params {
+        let mut params = Params::new();
 
         if let Some(color) = self.color {
-            params.push(color.param());
+            params = color.push_params(params, false);
+        }
+        if let Some(color) = self.background {
+            params = color.push_params(params, true);
         }
 
         /// Private macro for adding decoration codes to params
@@ -116,7 +306,7 @@ impl Style {
             ( $( $code: literal $name: ident ),* $(,)? ) => {
                 $(
                     if self.$name {
-                        params.push($code);
+                        params = params.push($code);
                     }
                 )*
             };
@@ -127,20 +317,83 @@ impl Style {
             2 dim,
             3 italic,
             4 underline,
+            5 blink,
+            7 reverse,
+            9 strikethrough,
         );
 
-        if params.is_empty() {
-            text.into()
+        params
+    }
+}
+
+/// ANSI parameters of a `Style`, as text separated with `;`
+///
+/// Fixed size, so parameters can be created in `const` context
+#[derive(Clone, Copy)]
+struct Params {
+    bytes: [u8; Self::CAPACITY],
+    len: usize,
+}
+
+impl Params {
+    /// Enough for foreground and background `Rgb` colors, and all decorations
+    const CAPACITY: usize = 64;
+
+    const fn new() -> Self {
+        Self {
+            bytes: [0; Self::CAPACITY],
+            len: 0,
+        }
+    }
+
+    /// Add a parameter, with a separator if needed
+    const fn push(mut self, param: u8) -> Self {
+        if self.len > 0 {
+            self.bytes[self.len] = b';';
+            self.len += 1;
+        }
+
+        let digits = [param / 100, param / 10 % 10, param % 10];
+        let skip = if param >= 100 {
+            0
+        } else if param >= 10 {
+            1
         } else {
-            format!(
-                "\x1b[{}m{text}\x1b[0m",
-                params
-                    .iter()
-                    .map(|x| x.to_string())
-                    .collect::<Vec<_>>()
-                    .join(";"),
-            )
+            2
+        };
+
+        let mut i = skip;
+        while i < digits.len() {
+            self.bytes[self.len] = b'0' + digits[i];
+            self.len += 1;
+            i += 1;
+        }
+
+        self
+    }
+
+    const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    const fn as_bytes(&self) -> &[u8] {
+        self.bytes.split_at(self.len).0
+    }
+
+    fn as_str(&self) -> &str {
+        std::str::from_utf8(self.as_bytes()).expect("parameters should be ascii")
+    }
+}
+
+/// Escape code to start a `Style`, from its parameters
+struct Prefix(Params);
+
+impl std::fmt::Display for Prefix {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return Ok(());
         }
+        write!(f, "\x1b[{}m", self.0.as_str())
     }
 }
 
@@ -0,0 +1,176 @@
+use std::fmt::Write;
+
+use crate::{Stream, Style};
+
+/// How a `Link` is written
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum LinkMode {
+    /// OSC 8 terminal hyperlink
+    #[default]
+    Hyperlink,
+    /// Text followed by the URL, such as `text (url)`
+    Inline,
+    /// Text only, without the style
+    Plain,
+}
+
+/// Terminal hyperlink, using OSC 8 escape codes
+///
+/// Create with `Link::new(url)`
+///
+/// Characters which cannot be written in the escape code, such as `ESC` or non-ASCII characters,
+/// are percent-encoded in the URL and id
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{style, Link, LinkMode, Stream};
+/// let link = Link::new("https://docs.rs/stilo").style(style!(Blue + u));
+///
+/// println!("{}", link.format("Docs"));
+///
+/// // Fallback when hyperlinks are not supported
+/// println!("{}", link.format_as("Docs", LinkMode::Inline));
+///
+/// // Only use a hyperlink if color is enabled for the stream
+/// println!("{}", link.format_for("Docs", Stream::Stdout));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Link {
+    url: String,
+    id: Option<String>,
+    style: Style,
+}
+
+impl Link {
+    /// Create a `Link` to a URL
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: encode(&url.into(), b""),
+            ..Default::default()
+        }
+    }
+
+    /// Add an id
+    ///
+    /// Terminals may treat links with the same id as one link, such as when wrapped over many lines.
+    /// `:` and `;` are percent-encoded
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(encode(&id.into(), b":;"));
+        self
+    }
+
+    /// Add a `Style` for the text
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Format text as a hyperlink
+    pub fn format(&self, text: &str) -> String {
+        self.format_as(text, LinkMode::Hyperlink)
+    }
+
+    /// Format text as a hyperlink, or with a fallback
+    pub fn format_as(&self, text: &str, mode: LinkMode) -> String {
+        match mode {
+            LinkMode::Hyperlink => {
+                let params = match &self.id {
+                    Some(id) => format!("id={id}"),
+                    None => String::new(),
+                };
+                let text = self.style.format(text);
+                format!("\x1b]8;{params};{}\x1b\\{text}\x1b]8;;\x1b\\", self.url)
+            }
+            LinkMode::Inline => format!("{} ({})", self.style.format(text), self.url),
+            LinkMode::Plain => text.to_string(),
+        }
+    }
+
+    /// Format text as a hyperlink if color is enabled for the stream
+    ///
+    /// Otherwise, the text is followed by the URL, without the style, such as `text (url)`.
+    /// See `Stream::color_enabled`
+    pub fn format_for(&self, text: &str, stream: Stream) -> String {
+        if stream.color_enabled() {
+            self.format(text)
+        } else {
+            format!("{text} ({})", self.url)
+        }
+    }
+}
+
+/// Percent-encode bytes which cannot be written in an OSC 8 escape code, and any reserved bytes
+///
+/// Only printable ASCII characters can be written
+fn encode(text: &str, reserved: &[u8]) -> String {
+    let mut output = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if (b' '..=b'~').contains(&byte) && !reserved.contains(&byte) {
+            output.push(byte as char);
+        } else {
+            let _ = write!(output, "%{byte:02X}");
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style;
+
+    #[test]
+    fn link_works() {
+        let link = Link::new("https://example.com");
+
+        assert_eq!(
+            link.format("Example"),
+            "\x1b]8;;https://example.com\x1b\\Example\x1b]8;;\x1b\\"
+        );
+        assert_eq!(
+            link.format_as("Example", LinkMode::Inline),
+            "Example (https://example.com)"
+        );
+        assert_eq!(link.format_as("Example", LinkMode::Plain), "Example");
+
+        let link = Link::new("file:///tmp/a.txt").id("a");
+        assert_eq!(
+            link.format("a.txt"),
+            "\x1b]8;id=a;file:///tmp/a.txt\x1b\\a.txt\x1b]8;;\x1b\\"
+        );
+    }
+
+    #[test]
+    fn link_works_w_style() {
+        let link = Link::new("https://example.com").style(style!(Blue + u));
+
+        assert_eq!(
+            link.format("Example"),
+            "\x1b]8;;https://example.com\x1b\\\x1b[34;4mExample\x1b[0m\x1b]8;;\x1b\\"
+        );
+        assert_eq!(
+            link.format_as("Example", LinkMode::Inline),
+            "\x1b[34;4mExample\x1b[0m (https://example.com)"
+        );
+        assert_eq!(link.format_as("Example", LinkMode::Plain), "Example");
+    }
+
+    #[test]
+    fn link_works_w_unsafe_chars() {
+        let link = Link::new("https://x.y/\x1b]8;;evil\x07é").id("a;b:c\x1b\\");
+
+        assert_eq!(
+            link.format("x"),
+            "\x1b]8;id=a%3Bb%3Ac%1B\\;https://x.y/%1B]8;;evil%07%C3%A9\x1b\\x\x1b]8;;\x1b\\"
+        );
+        assert_eq!(
+            link.format_as("x", LinkMode::Inline),
+            "x (https://x.y/%1B]8;;evil%07%C3%A9)"
+        );
+        assert_eq!(
+            Link::new("https://x.y/a%20b?c=d;e").format_as("x", LinkMode::Inline),
+            "x (https://x.y/a%20b?c=d;e)"
+        );
+    }
+}
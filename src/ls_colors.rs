@@ -0,0 +1,354 @@
+use std::{collections::HashMap, env, fs, path::Path};
+
+use crate::Style;
+
+/// Styles for file paths, parsed from `LS_COLORS`
+///
+/// Indicators for file types (such as `di` or `ex`) and globs for file names (such as `*.rs`) are
+/// supported. SGR codes which cannot be represented as a `Style` are ignored.
+///
+/// Globs ignore case, like GNU `ls`, unless another glob differs only in case
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{style, LsColors, Color::*};
+/// let colors = LsColors::parse("di=01;34:*.rs=38;5;208");
+///
+/// assert_eq!(colors.indicator("di"), Some(style!(Blue + bold)));
+/// assert_eq!(colors.style_for_name("main.rs"), Some(style!().color(Fixed(208))));
+///
+/// // Read from environment
+/// let colors = LsColors::from_env().unwrap_or_default();
+/// if let Some(style) = colors.style_for_path("Cargo.toml") {
+///     println!("{}", style.format("Cargo.toml"));
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LsColors {
+    indicators: HashMap<String, Style>,
+    globs: Vec<Glob>,
+    /// Whether `ln=target` was given
+    link_target: bool,
+}
+
+impl LsColors {
+    /// Parse `LS_COLORS` syntax, such as `di=01;34:*.rs=38;5;208`
+    pub fn parse(input: &str) -> Self {
+        let mut colors = Self::default();
+
+        for entry in input.split(':') {
+            let Some((key, params)) = entry.split_once('=') else {
+                continue;
+            };
+
+            if key == "ln" && params == "target" {
+                colors.link_target = true;
+                continue;
+            }
+
+            let style = Style::from_sgr_lossy(params);
+
+            if let Some(suffix) = key.strip_prefix('*') {
+                colors.globs.push(Glob {
+                    suffix: suffix.to_string(),
+                    style,
+                    case_sensitive: false,
+                });
+            } else {
+                colors.indicators.insert(key.to_string(), style);
+            }
+        }
+
+        // Globs which differ only in case, such as `*.c` and `*.C`, must match case
+        for i in 0..colors.globs.len() {
+            let suffix = &colors.globs[i].suffix;
+            colors.globs[i].case_sensitive = colors
+                .globs
+                .iter()
+                .any(|glob| glob.suffix != *suffix && glob.suffix.eq_ignore_ascii_case(suffix));
+        }
+
+        colors
+    }
+
+    /// Parse the `LS_COLORS` environment variable
+    ///
+    /// Returns `None` if the variable is not set
+    pub fn from_env() -> Option<Self> {
+        env::var("LS_COLORS").ok().map(|value| Self::parse(&value))
+    }
+
+    /// Get the `Style` of an indicator, such as `di` or `ex`
+    pub fn indicator(&self, key: &str) -> Option<Style> {
+        self.indicators.get(key).copied()
+    }
+
+    /// Get the `Style` of the last glob which matches a file name, such as `*.rs`
+    pub fn style_for_name(&self, name: &str) -> Option<Style> {
+        self.globs
+            .iter()
+            .rev()
+            .find(|glob| glob.matches(name))
+            .map(|glob| glob.style)
+    }
+
+    /// Get the `Style` for a path, reading its metadata from the file system
+    pub fn style_for_path(&self, path: impl AsRef<Path>) -> Option<Style> {
+        let path = path.as_ref();
+        self.style_for(path, fs::symlink_metadata(path).ok().as_ref())
+    }
+
+    /// Get the `Style` for a path, with its metadata from `fs::symlink_metadata`
+    ///
+    /// Missing metadata uses the `mi` indicator.
+    /// Symbolic links use the style of their target if `ln=target` is given.
+    pub fn style_for(&self, path: &Path, metadata: Option<&fs::Metadata>) -> Option<Style> {
+        let Some(metadata) = metadata else {
+            return self.indicator("mi");
+        };
+        let file_type = metadata.file_type();
+
+        if file_type.is_symlink() {
+            return match fs::metadata(path) {
+                Ok(target) if self.link_target => {
+                    let target_path = fs::read_link(path).unwrap_or_default();
+                    self.style_for(&target_path, Some(&target))
+                }
+                Ok(_) => self.indicator("ln"),
+                Err(_) => self.indicator("or").or_else(|| self.indicator("ln")),
+            };
+        }
+
+        if file_type.is_dir() {
+            return self
+                .dir_indicator(metadata)
+                .or_else(|| self.indicator("di"));
+        }
+
+        if file_type.is_file() {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            return self
+                .file_indicator(metadata)
+                .or_else(|| self.style_for_name(&name))
+                .or_else(|| self.indicator("fi"));
+        }
+
+        self.special_indicator(metadata)
+    }
+
+    /// Get the indicator for a directory with special permissions
+    #[cfg(unix)]
+    fn dir_indicator(&self, metadata: &fs::Metadata) -> Option<Style> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = metadata.permissions().mode();
+        let sticky = mode & 0o1000 != 0;
+        let other_writable = mode & 0o002 != 0;
+
+        self.first_indicator(&[
+            (sticky && other_writable, "tw"),
+            (other_writable, "ow"),
+            (sticky, "st"),
+        ])
+    }
+
+    /// Get the indicator for a regular file with special permissions, or multiple hard links
+    #[cfg(unix)]
+    fn file_indicator(&self, metadata: &fs::Metadata) -> Option<Style> {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+        let mode = metadata.permissions().mode();
+
+        self.first_indicator(&[
+            (mode & 0o4000 != 0, "su"),
+            (mode & 0o2000 != 0, "sg"),
+            (mode & 0o111 != 0, "ex"),
+            (metadata.nlink() > 1, "mh"),
+        ])
+    }
+
+    /// Get the first indicator which is given, out of those which apply
+    #[cfg(unix)]
+    fn first_indicator(&self, checks: &[(bool, &str)]) -> Option<Style> {
+        checks
+            .iter()
+            .filter(|(applies, _)| *applies)
+            .find_map(|(_, key)| self.indicator(key))
+    }
+
+    /// Get the indicator for a pipe, socket, or device
+    #[cfg(unix)]
+    fn special_indicator(&self, metadata: &fs::Metadata) -> Option<Style> {
+        use std::os::unix::fs::FileTypeExt;
+
+        let file_type = metadata.file_type();
+        let key = if file_type.is_fifo() {
+            "pi"
+        } else if file_type.is_socket() {
+            "so"
+        } else if file_type.is_block_device() {
+            "bd"
+        } else if file_type.is_char_device() {
+            "cd"
+        } else {
+            return None;
+        };
+
+        self.indicator(key)
+    }
+
+    #[cfg(not(unix))]
+    fn dir_indicator(&self, _metadata: &fs::Metadata) -> Option<Style> {
+        None
+    }
+
+    #[cfg(not(unix))]
+    fn file_indicator(&self, _metadata: &fs::Metadata) -> Option<Style> {
+        None
+    }
+
+    #[cfg(not(unix))]
+    fn special_indicator(&self, _metadata: &fs::Metadata) -> Option<Style> {
+        None
+    }
+}
+
+/// Glob for the end of a file name, such as `*.rs`
+#[derive(Debug, Clone, PartialEq)]
+struct Glob {
+    suffix: String,
+    style: Style,
+    case_sensitive: bool,
+}
+
+impl Glob {
+    /// Check if a file name ends with the suffix
+    fn matches(&self, name: &str) -> bool {
+        if self.case_sensitive {
+            return name.ends_with(self.suffix.as_str());
+        }
+        let (name, suffix) = (name.as_bytes(), self.suffix.as_bytes());
+        name.len() >= suffix.len() && name[name.len() - suffix.len()..].eq_ignore_ascii_case(suffix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use super::*;
+    use crate::{style, Color::*};
+
+    /// Create an empty directory for test files
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("stilo-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parse_works() {
+        let colors =
+            LsColors::parse("rs=0:di=01;34:ln=01;36:*.rs=38;5;208:*.tar=01;31:bad=x;97:*.RS=32:");
+
+        assert_eq!(colors.indicator("rs"), Some(style!()));
+        assert_eq!(colors.indicator("di"), Some(style!(Blue + bold)));
+        assert_eq!(colors.indicator("ln"), Some(style!(Cyan + bold)));
+        assert_eq!(colors.indicator("bad"), Some(style!().color(Fixed(15))));
+        assert_eq!(colors.indicator("fi"), None);
+
+        assert_eq!(
+            colors.style_for_name("main.rs"),
+            Some(style!().color(Fixed(208)))
+        );
+        assert_eq!(colors.style_for_name("MAIN.RS"), Some(style!(Green)));
+        assert_eq!(colors.style_for_name("main.Rs"), None);
+        assert_eq!(colors.style_for_name("a.tar"), Some(style!(Red + bold)));
+        assert_eq!(colors.style_for_name("A.TAR"), Some(style!(Red + bold)));
+        assert_eq!(colors.style_for_name("a.tar.gz"), None);
+
+        assert_eq!(LsColors::parse(""), LsColors::default());
+    }
+
+    #[test]
+    fn parse_works_w_unsupported_codes() {
+        let colors = LsColors::parse("di=01;34;53:ex=38;5:*.md=6;33");
+
+        assert_eq!(colors.indicator("di"), Some(style!(Blue + bold)));
+        assert_eq!(colors.indicator("ex"), Some(style!()));
+        assert_eq!(colors.style_for_name("README.md"), Some(style!(Yellow)));
+    }
+
+    #[test]
+    fn later_globs_take_priority() {
+        let colors = LsColors::parse("*.gz=31:*.tar.gz=32");
+        assert_eq!(colors.style_for_name("a.tar.gz"), Some(style!(Green)));
+
+        let colors = LsColors::parse("*.tar.gz=32:*.gz=31");
+        assert_eq!(colors.style_for_name("a.tar.gz"), Some(style!(Red)));
+    }
+
+    #[test]
+    fn style_for_path_works() {
+        let dir = test_dir("ls-colors");
+        fs::write(dir.join("main.rs"), "").unwrap();
+        fs::write(dir.join("notes"), "").unwrap();
+
+        let colors = LsColors::parse("di=01;34:fi=0:*.rs=33");
+        assert_eq!(colors.style_for_path(&dir), Some(style!(Blue + bold)));
+        assert_eq!(
+            colors.style_for_path(dir.join("main.rs")),
+            Some(style!(Yellow))
+        );
+        assert_eq!(colors.style_for_path(dir.join("notes")), Some(style!()));
+        assert_eq!(colors.style_for_path(dir.join("missing")), None);
+
+        let colors = LsColors::parse("mi=31");
+        assert_eq!(
+            colors.style_for_path(dir.join("missing")),
+            Some(style!(Red))
+        );
+        assert_eq!(colors.style_for_path(dir.join("notes")), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn style_for_path_works_w_permissions_and_links() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let dir = test_dir("ls-colors-unix");
+        let script = dir.join("run.sh");
+        fs::write(&script, "").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let shared = dir.join("shared");
+        fs::create_dir(&shared).unwrap();
+        fs::set_permissions(&shared, fs::Permissions::from_mode(0o1777)).unwrap();
+        symlink(&script, dir.join("link")).unwrap();
+        symlink(dir.join("missing"), dir.join("broken")).unwrap();
+
+        let colors = LsColors::parse("di=34:tw=30;42:ex=32:*.sh=33:ln=36:or=31");
+        assert_eq!(colors.style_for_path(&script), Some(style!(Green)));
+        assert_eq!(
+            colors.style_for_path(&shared),
+            Some(style!(Black).background(Green))
+        );
+        assert_eq!(colors.style_for_path(dir.join("link")), Some(style!(Cyan)));
+        assert_eq!(colors.style_for_path(dir.join("broken")), Some(style!(Red)));
+
+        // Fall back to more general indicators
+        let colors = LsColors::parse("di=34:*.sh=33:ln=target");
+        assert_eq!(colors.style_for_path(&script), Some(style!(Yellow)));
+        assert_eq!(colors.style_for_path(&shared), Some(style!(Blue)));
+        assert_eq!(
+            colors.style_for_path(dir.join("link")),
+            Some(style!(Yellow))
+        );
+        assert_eq!(colors.style_for_path(dir.join("broken")), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
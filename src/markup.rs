@@ -0,0 +1,385 @@
+/// Format text with inline style tags, checked at compile time.
+///
+/// Similar to `stylize_many!`, but styles are written inside the text.
+///
+/// Tags use the same syntax as parsing a `Style`, such as `<red+bold>` or `<+i>`, and are closed
+/// with `</>`. Tags can be nested. A literal `<` is written as `<<`.
+///
+/// The syntax of `parse_markup` is also accepted, such as `[red+bold]` and `[/]`, so templates can
+/// be moved between the two. Literal brackets are then written as `[[` and `]]`.
+///
+/// Text is a format string, with arguments given after it.
+/// Expands to a single `format!` call, with escape codes in the format string.
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::markup;
+/// let name = "World";
+///
+/// // Red and bold, then italic
+/// println!("{}", markup!("Hello <red+bold>{name}</> and <i>more</>"));
+///
+/// // Nested tags, and format arguments
+/// println!("{}", markup!("<Green>Passed <b>{}</> tests</>", 12));
+///
+/// // Literal `<`
+/// println!("{}", markup!("<cyan>1 << 2</>"));
+///
+/// // Same syntax as `parse_markup`
+/// println!("{}", markup!("[red+bold]error[/]: [[{}]]", 1));
+/// ```
+///
+/// Invalid tags are a compile error
+///
+/// ```compile_fail
+/// # use stilo::markup;
+/// println!("{}", markup!("Hello <purple>World</>"));
+/// ```
+pub use stilo_macros::markup;
+
+use std::{error::Error, fmt};
+
+use crate::{ParseStyleError, Style, StyledString};
+
+/// Error from parsing markup at runtime, with the byte offset in the template
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkupError {
+    /// Byte offset of the tag or argument
+    pub offset: usize,
+    /// Kind of error
+    pub kind: MarkupErrorKind,
+}
+
+/// Kind of `MarkupError`
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarkupErrorKind {
+    /// Tag has no closing `]`
+    UnterminatedTag,
+    /// Tag has no style, such as `[]`
+    EmptyTag,
+    /// Closing tag `[/]` has no opening tag
+    UnexpectedClose,
+    /// Tag is never closed with `[/]`
+    UnclosedTag,
+    /// Tag is not a valid `Style`
+    InvalidStyle(ParseStyleError),
+    /// Argument has no closing `}`
+    UnterminatedArgument,
+    /// Argument is not given
+    UnknownArgument(String),
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            MarkupErrorKind::UnterminatedTag => {
+                write!(f, "unterminated tag, use `[[` for a literal `[`")?
+            }
+            MarkupErrorKind::EmptyTag => write!(f, "tag has no style")?,
+            MarkupErrorKind::UnexpectedClose => {
+                write!(f, "closing tag has no matching opening tag")?
+            }
+            MarkupErrorKind::UnclosedTag => write!(f, "tag is not closed, use `[/]`")?,
+            MarkupErrorKind::InvalidStyle(error) => write!(f, "{error}")?,
+            MarkupErrorKind::UnterminatedArgument => {
+                write!(f, "unterminated argument, use `{{{{` for a literal `{{`")?
+            }
+            MarkupErrorKind::UnknownArgument(name) => write!(f, "unknown argument `{name}`")?,
+        }
+        write!(f, " at offset {}", self.offset)
+    }
+}
+
+impl Error for MarkupError {}
+
+/// Parse text with inline style tags at runtime, into a `StyledString`
+///
+/// Tags use the same syntax as parsing a `Style`, such as `[red+bold]` or `[+i]`, and are closed
+/// with `[/]`. Tags can be nested. Literal brackets and braces are written twice, such as `[[`.
+///
+/// Brackets are used instead of the `<red+bold>` tags of `markup!`, so templates can contain `<`
+/// without escaping. `markup!` also accepts this syntax.
+///
+/// Use `parse_markup_with` to replace arguments, such as `{msg}`.
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::parse_markup;
+/// let text = parse_markup("[red+bold]error[/]: [[1]] not found").unwrap();
+///
+/// println!("{}", text);
+/// assert_eq!(text.plain(), "error: [1] not found");
+///
+/// let error = parse_markup("[purple]error[/]").unwrap_err();
+/// assert_eq!(error.offset, 0);
+/// ```
+pub fn parse_markup(template: &str) -> Result<StyledString, MarkupError> {
+    parse_markup_with(template, |_| None)
+}
+
+/// Parse text with inline style tags at runtime, replacing arguments
+///
+/// Arguments such as `{msg}` are replaced with the value given by `args`, and have the style of
+/// the surrounding tags. Argument values are not parsed as markup.
+///
+/// See `parse_markup` for syntax.
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::parse_markup_with;
+/// let template = "[red+bold]error[/]: [i]{msg}[/]";
+/// let text = parse_markup_with(template, |name| match name {
+///     "msg" => Some("file not found".to_string()),
+///     _ => None,
+/// })
+/// .unwrap();
+///
+/// println!("{}", text);
+/// assert_eq!(text.plain(), "error: file not found");
+/// ```
+pub fn parse_markup_with(
+    template: &str,
+    args: impl Fn(&str) -> Option<String>,
+) -> Result<StyledString, MarkupError> {
+    let mut output = StyledString::new();
+    let mut text = String::new();
+    // Open tags, with their offsets, and the merged style of each
+    let mut stack: Vec<(usize, Style)> = Vec::new();
+    let mut chars = template.char_indices().peekable();
+
+    let error = |offset, kind| MarkupError { offset, kind };
+
+    while let Some((offset, ch)) = chars.next() {
+        let style = stack.last().map(|(_, style)| *style).unwrap_or_default();
+
+        match ch {
+            '[' | ']' | '{' | '}' if chars.next_if(|(_, next)| *next == ch).is_some() => {
+                text.push(ch)
+            }
+
+            '[' => {
+                let tag = read_until(&mut chars, ']')
+                    .ok_or_else(|| error(offset, MarkupErrorKind::UnterminatedTag))?;
+                output.push(std::mem::take(&mut text), style);
+
+                if tag.trim().is_empty() {
+                    return Err(error(offset, MarkupErrorKind::EmptyTag));
+                }
+                if tag == "/" {
+                    stack
+                        .pop()
+                        .ok_or_else(|| error(offset, MarkupErrorKind::UnexpectedClose))?;
+                } else {
+                    let tag_style = tag
+                        .parse()
+                        .map_err(|err| error(offset, MarkupErrorKind::InvalidStyle(err)))?;
+                    stack.push((offset, style.merge(tag_style)));
+                }
+            }
+
+            '{' => {
+                let name = read_until(&mut chars, '}')
+                    .ok_or_else(|| error(offset, MarkupErrorKind::UnterminatedArgument))?;
+                let value = args(name.trim()).ok_or_else(|| {
+                    error(offset, MarkupErrorKind::UnknownArgument(name.trim().into()))
+                })?;
+                text += &value;
+            }
+
+            _ => text.push(ch),
+        }
+    }
+
+    if let Some((offset, _)) = stack.last() {
+        return Err(error(*offset, MarkupErrorKind::UnclosedTag));
+    }
+
+    output.push(text, Style::new());
+    Ok(output)
+}
+
+/// Read characters until a closing character, which is consumed
+///
+/// Returns `None` if the closing character is not found
+fn read_until(
+    chars: &mut std::iter::Peekable<std::str::CharIndices>,
+    close: char,
+) -> Option<String> {
+    let mut text = String::new();
+    for (_, ch) in chars.by_ref() {
+        if ch == close {
+            return Some(text);
+        }
+        text.push(ch);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{markup, style, stylize, stylize_many, Color::*};
+
+    #[test]
+    fn markup_works() {
+        let world = "World!";
+
+        assert_eq!(markup!("Hello"), "Hello");
+        assert_eq!(markup!("<Red>Hello</>"), stylize!("Hello": Red));
+        assert_eq!(markup!("<red+bold>Hello</>"), stylize!("Hello": Red + bold));
+        assert_eq!(markup!("<+u+d+bold>Hello</>"), stylize!("Hello": +u+d+bold));
+
+        assert_eq!(markup!("Hello {}", world), "Hello World!");
+        assert_eq!(
+            markup!("Hello <Green>{world}</>"),
+            stylize_many!("Hello "; "{world}": Green)
+        );
+        assert_eq!(
+            markup!("<b>Hello</> <i>{}</> {}", world, 123,),
+            stylize_many!("Hello": +b; " "; "{}": +i, world; " {}", 123)
+        );
+        assert_eq!(markup!("<blue>{{x}} << y</>"), stylize!("{{x}} < y": Blue));
+    }
+
+    #[test]
+    fn markup_matches_parse_markup() {
+        for (compiled, template) in [
+            (markup!("[red+bold]error[/]: x"), "[red+bold]error[/]: x"),
+            (markup!("[Green]a[/] [u]b[/]"), "[Green]a[/] [u]b[/]"),
+            (markup!("[[1]] [b]]][/]"), "[[1]] [b]]][/]"),
+        ] {
+            assert_eq!(compiled, parse_markup(template).unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn markup_works_w_nesting() {
+        assert_eq!(
+            markup!("<red>a <b>b</> c</>"),
+            "\x1b[31ma \x1b[31;1mb\x1b[0m\x1b[31m c\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn parse_markup_works() {
+        let parse = |template| parse_markup(template).unwrap();
+
+        assert_eq!(parse(""), StyledString::new());
+        assert_eq!(parse("Hello"), StyledString::from("Hello"));
+        assert_eq!(parse("[red]Hello[/]").to_string(), stylize!("Hello": Red));
+        assert_eq!(
+            parse("[red+bold]error[/]: oops").to_string(),
+            stylize_many!("error": Red + bold; ": oops")
+        );
+        assert_eq!(
+            parse("[+i]a[/][u]b[/]").spans(),
+            [("a", style!(+i)), ("b", style!(+u))]
+                .into_iter()
+                .collect::<StyledString>()
+                .spans()
+        );
+        assert_eq!(parse("[[1]] {{x}} ] }").plain(), "[1] {x} ] }");
+        assert_eq!(parse("]]] }}}").plain(), "]] }}");
+        assert_eq!(
+            parse("[Red on Blue]x[/]").spans()[0].style,
+            style!(Red).background(Blue)
+        );
+    }
+
+    #[test]
+    fn parse_markup_works_w_nesting() {
+        let text = parse_markup("[red]a [b]b [blue]c[/][/] d[/]").unwrap();
+
+        let expected: StyledString = [
+            ("a ", style!(Red)),
+            ("b ", style!(Red + b)),
+            ("c", style!(Blue + b)),
+            (" d", style!(Red)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn parse_markup_works_w_args() {
+        let args = |name: &str| match name {
+            "msg" => Some("[not] {markup}".to_string()),
+            "n" => Some("3".to_string()),
+            _ => None,
+        };
+
+        let text = parse_markup_with("[red]error[/]: [i]{msg} ({ n })[/]", args).unwrap();
+        let expected: StyledString = [
+            ("error", style!(Red)),
+            (": ", style!()),
+            ("[not] {markup} (3)", style!(+i)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn parse_markup_errors() {
+        let error = |template| parse_markup(template).unwrap_err();
+
+        assert_eq!(
+            error("ab [red"),
+            MarkupError {
+                offset: 3,
+                kind: MarkupErrorKind::UnterminatedTag
+            }
+        );
+        assert_eq!(
+            error("ab [/]"),
+            MarkupError {
+                offset: 3,
+                kind: MarkupErrorKind::UnexpectedClose
+            }
+        );
+        assert_eq!(
+            error("[b]ab [red]x[/]"),
+            MarkupError {
+                offset: 0,
+                kind: MarkupErrorKind::UnclosedTag
+            }
+        );
+        assert_eq!(
+            error("é [purple]x[/]"),
+            MarkupError {
+                offset: 3,
+                kind: MarkupErrorKind::InvalidStyle(ParseStyleError::UnknownName("purple".into()))
+            }
+        );
+        assert_eq!(
+            error("a [ ]x"),
+            MarkupError {
+                offset: 2,
+                kind: MarkupErrorKind::EmptyTag
+            }
+        );
+        assert_eq!(
+            error("x {msg"),
+            MarkupError {
+                offset: 2,
+                kind: MarkupErrorKind::UnterminatedArgument
+            }
+        );
+        assert_eq!(
+            error("x {msg}"),
+            MarkupError {
+                offset: 2,
+                kind: MarkupErrorKind::UnknownArgument("msg".into())
+            }
+        );
+
+        assert_eq!(
+            error("[/]").to_string(),
+            "closing tag has no matching opening tag at offset 0"
+        );
+    }
+}
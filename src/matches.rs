@@ -0,0 +1,239 @@
+use crate::{
+    terminal::{escape_len, update_style_state},
+    Color::*,
+    Style,
+};
+
+/// Highlight matches of literal patterns in text, like `grep --color`
+///
+/// Existing ANSI styling of the text is kept, and restored after each match.
+/// Where patterns overlap, the longest match at the earliest position is used.
+///
+/// Create with `MatchHighlighter::new(patterns)`
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{style, stylize, MatchHighlighter};
+/// let highlighter = MatchHighlighter::new(["error", "warn"])
+///     .ignore_case()
+///     .style(style!(Yellow + bold));
+///
+/// let line = stylize!("ERROR: disk full": Red);
+/// println!("{}", highlighter.highlight(&line));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchHighlighter {
+    patterns: Vec<Vec<char>>,
+    ignore_case: bool,
+    style: Style,
+}
+
+impl MatchHighlighter {
+    /// Create a `MatchHighlighter` for literal patterns
+    ///
+    /// Empty patterns are ignored. The default style is red and bold, the same as grep
+    pub fn new<T: Into<String>>(patterns: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            patterns: patterns
+                .into_iter()
+                .map(|pattern| pattern.into().chars().collect::<Vec<_>>())
+                .filter(|pattern| !pattern.is_empty())
+                .collect(),
+            ignore_case: false,
+            style: Style::new().color(Red).bold(),
+        }
+    }
+
+    /// Match patterns ignoring case
+    pub fn ignore_case(mut self) -> Self {
+        self.ignore_case = true;
+        self
+    }
+
+    /// Add a `Style` for matches
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Highlight all matches in text
+    ///
+    /// Escape codes in the text are not matched, and matches can contain escape codes
+    pub fn highlight(&self, text: &str) -> String {
+        let prefix = self.style.prefix().to_string();
+        if prefix.is_empty() || self.patterns.is_empty() {
+            return text.to_string();
+        }
+
+        // Visible characters, with escape sequences separate
+        let mut chars = Vec::new();
+        let mut escapes = Vec::new();
+        let mut rest = text;
+        while let Some(ch) = rest.chars().next() {
+            let len = escape_len(rest);
+            if len > 0 {
+                escapes.push((chars.len(), &rest[..len]));
+                rest = &rest[len..];
+            } else {
+                chars.push(ch);
+                rest = &rest[ch.len_utf8()..];
+            }
+        }
+
+        let matched = self.find_matches(&chars);
+
+        let mut output = String::with_capacity(text.len());
+        // Escape codes which apply the current style of the text
+        let mut state = String::new();
+        let mut in_match = false;
+        let mut escapes = escapes.into_iter().peekable();
+
+        for (i, ch) in chars.iter().enumerate() {
+            // End a match before escapes, and start one after, so escapes between are not styled
+            if in_match && !matched[i] {
+                in_match = false;
+                output += "\x1b[0m";
+                output += &state;
+            }
+            while let Some((_, escape)) = escapes.next_if(|(index, _)| *index == i) {
+                write_escape(&mut output, &mut state, escape, in_match.then_some(&prefix));
+            }
+            if !in_match && matched[i] {
+                in_match = true;
+                output += &prefix;
+            }
+            output.push(*ch);
+        }
+
+        for (_, escape) in escapes {
+            write_escape(&mut output, &mut state, escape, in_match.then_some(&prefix));
+        }
+        if in_match {
+            output += "\x1b[0m";
+            output += &state;
+        }
+
+        output
+    }
+
+    /// Check which characters are in a match
+    fn find_matches(&self, chars: &[char]) -> Vec<bool> {
+        let mut matched = vec![false; chars.len()];
+        let mut i = 0;
+
+        while i < chars.len() {
+            let len = self
+                .patterns
+                .iter()
+                .filter(|pattern| self.matches_at(&chars[i..], pattern))
+                .map(Vec::len)
+                .max();
+
+            match len {
+                Some(len) => {
+                    matched[i..i + len].fill(true);
+                    i += len;
+                }
+                None => i += 1,
+            }
+        }
+
+        matched
+    }
+
+    /// Check if characters start with a pattern
+    fn matches_at(&self, chars: &[char], pattern: &[char]) -> bool {
+        chars.len() >= pattern.len()
+            && chars
+                .iter()
+                .zip(pattern)
+                .all(|(a, b)| a == b || (self.ignore_case && a.to_lowercase().eq(b.to_lowercase())))
+    }
+}
+
+/// Write an escape sequence from the text, and update the current style
+///
+/// Style codes in a match are followed by the match style, so the match stays highlighted
+fn write_escape(
+    output: &mut String,
+    state: &mut String,
+    escape: &str,
+    match_prefix: Option<&String>,
+) {
+    output.push_str(escape);
+
+    if update_style_state(state, escape) {
+        if let Some(prefix) = match_prefix {
+            output.push_str(prefix);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{strip_ansi, style, stylize, Link};
+
+    #[test]
+    fn highlight_works() {
+        let highlighter = MatchHighlighter::new(["lo", "wor"]).style(style!(Red));
+
+        assert_eq!(
+            highlighter.highlight("hello world"),
+            "hel\x1b[31mlo\x1b[0m \x1b[31mwor\x1b[0mld"
+        );
+        assert_eq!(highlighter.highlight("nothing"), "nothing");
+        assert_eq!(highlighter.highlight(""), "");
+        assert_eq!(highlighter.highlight("lolo"), "\x1b[31mlolo\x1b[0m");
+    }
+
+    #[test]
+    fn highlight_works_w_many_patterns() {
+        let highlighter = MatchHighlighter::new(["ab", "abc", "", "c"]).style(style!(+u));
+
+        assert_eq!(
+            highlighter.highlight("abcd c"),
+            "\x1b[4mabc\x1b[0md \x1b[4mc\x1b[0m"
+        );
+
+        let highlighter = MatchHighlighter::new(Vec::<String>::new());
+        assert_eq!(highlighter.highlight("abc"), "abc");
+        let highlighter = MatchHighlighter::new(["a"]).style(style!());
+        assert_eq!(highlighter.highlight("abc"), "abc");
+    }
+
+    #[test]
+    fn highlight_works_w_ignore_case() {
+        let highlighter = MatchHighlighter::new(["héllo"]).style(style!(Red));
+
+        assert_eq!(highlighter.highlight("HÉLLO"), "HÉLLO");
+        assert_eq!(
+            highlighter.clone().ignore_case().highlight("a HÉLLO b"),
+            "a \x1b[31mHÉLLO\x1b[0m b"
+        );
+    }
+
+    #[test]
+    fn highlight_keeps_styles() {
+        let highlighter = MatchHighlighter::new(["world"]).style(style!(+b));
+
+        // Style is restored after the match
+        assert_eq!(
+            highlighter.highlight(&stylize!("hello world!": Red)),
+            "\x1b[31mhello \x1b[1mworld\x1b[0m\x1b[31m!\x1b[0m"
+        );
+
+        // Style codes in a match are followed by the match style
+        assert_eq!(
+            highlighter.highlight("wo\x1b[32mrld\x1b[0m x"),
+            "\x1b[1mwo\x1b[32m\x1b[1mrld\x1b[0m\x1b[32m\x1b[0m x"
+        );
+
+        // Hyperlinks are kept
+        let link = Link::new("https://x.y").format("world");
+        let text = highlighter.highlight(&link);
+        assert_eq!(strip_ansi(&text), "world");
+        assert!(text.contains("\x1b]8;;https://x.y\x1b\\"));
+    }
+}
@@ -0,0 +1,246 @@
+use std::fmt;
+
+use crate::{Border, Style, StyledString};
+
+/// Box with a border around styled text, and an optional title
+///
+/// Content can be a `StyledString`, or text with ANSI codes, such as from `stylize!`.
+/// Widths are measured in terminal columns, without ANSI codes, so wide characters take 2.
+///
+/// Create with `Panel::new(content)`
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{style, stylize, Border, Panel};
+/// let panel = Panel::new(stylize!("Something went wrong\nTry again": Red))
+///     .title(stylize!("Error": Red + b))
+///     .border(Border::Rounded)
+///     .border_style(style!(Red));
+///
+/// println!("{}", panel);
+/// assert_eq!(
+///     stilo::strip_ansi(&panel.to_string()),
+///     "\
+/// ╭─ Error ──────────────╮
+/// │ Something went wrong │
+/// │ Try again            │
+/// ╰──────────────────────╯"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Panel {
+    content: StyledString,
+    title: Option<StyledString>,
+    title_style: Style,
+    border: Border,
+    border_style: Style,
+    padding: (usize, usize),
+}
+
+impl Panel {
+    /// Create a `Panel` around content, with a single border and padding of 1 space on each side
+    pub fn new(content: impl Into<StyledString>) -> Self {
+        Self {
+            content: content.into(),
+            title: None,
+            title_style: Style::new().bold(),
+            border: Border::Single,
+            border_style: Style::new(),
+            padding: (0, 1),
+        }
+    }
+
+    /// Add a title, in the top border
+    pub fn title(mut self, title: impl Into<StyledString>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Add a `Style` for the title
+    ///
+    /// Styles of the title text are applied on top
+    pub fn title_style(mut self, style: Style) -> Self {
+        self.title_style = style;
+        self
+    }
+
+    /// Set the `Border` preset
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Add a `Style` for the border
+    pub fn border_style(mut self, style: Style) -> Self {
+        self.border_style = style;
+        self
+    }
+
+    /// Set the number of empty lines above and below content, and spaces before and after
+    pub fn padding(mut self, vertical: usize, horizontal: usize) -> Self {
+        self.padding = (vertical, horizontal);
+        self
+    }
+
+    /// Format the panel, with lines separated by newlines
+    pub fn render(&self) -> StyledString {
+        let (vertical, horizontal) = self.padding;
+        let lines = self.content.lines();
+        let title_width = self.title.as_ref().map(|title| title.width());
+
+        let content_width = lines
+            .iter()
+            .map(|line| line.width())
+            .max()
+            .unwrap_or(0);
+        let mut width = content_width + 2 * horizontal;
+        if let Some(title_width) = title_width {
+            // Title with a space on each side, and a line before and after
+            width = width.max(title_width + 4);
+        }
+
+        let chars = self.border.chars();
+        let mut rows = Vec::new();
+
+        // Top border, with title
+        match (chars, &self.title) {
+            (Some(chars), title) => {
+                let mut line = StyledString::new();
+                line.push(chars.top_left.to_string(), self.border_style);
+                let mut rest = width;
+                if let Some(title) = title {
+                    line.push(format!("{} ", chars.horizontal), self.border_style);
+                    line.append_under(title, self.title_style);
+                    line.push(" ", self.border_style);
+                    rest -= title.width() + 3;
+                }
+                line.push(chars.horizontal.to_string().repeat(rest), self.border_style);
+                line.push(chars.top_right.to_string(), self.border_style);
+                rows.push(line);
+            }
+            (None, Some(title)) => {
+                let mut line = StyledString::new();
+                line.append_under(title, self.title_style);
+                rows.push(line);
+            }
+            (None, None) => (),
+        }
+
+        let empty = vec![StyledString::new(); vertical];
+        for line in empty.iter().chain(&lines).chain(&empty) {
+            let mut row = StyledString::new();
+            row.push_plain(" ".repeat(horizontal));
+            row.append(line.clone());
+
+            // Spaces after content are only needed before a border
+            if let Some(chars) = chars {
+                row.push_plain(" ".repeat(width - horizontal - line.width()));
+                row.push(chars.vertical.to_string(), self.border_style);
+
+                let mut line = StyledString::new();
+                line.push(chars.vertical.to_string(), self.border_style);
+                line.append(row);
+                row = line;
+            }
+            rows.push(row);
+        }
+
+        if let Some(chars) = chars {
+            let mut line = StyledString::new();
+            line.push(
+                format!(
+                    "{}{}{}",
+                    chars.bottom_left,
+                    chars.horizontal.to_string().repeat(width),
+                    chars.bottom_right
+                ),
+                self.border_style,
+            );
+            rows.push(line);
+        }
+
+        let mut output = StyledString::new();
+        for (i, row) in rows.into_iter().enumerate() {
+            if i > 0 {
+                output.push_plain("\n");
+            }
+            output.append(row);
+        }
+        output
+    }
+}
+
+impl fmt::Display for Panel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{style, stylize};
+
+    #[test]
+    fn panel_works() {
+        assert_eq!(
+            Panel::new("ab\nc").to_string(),
+            "┌────┐\n│ ab │\n│ c  │\n└────┘"
+        );
+        assert_eq!(
+            Panel::new("").border(Border::Ascii).to_string(),
+            "+--+\n|  |\n+--+"
+        );
+        assert_eq!(
+            Panel::new("a")
+                .padding(1, 2)
+                .border(Border::Double)
+                .to_string(),
+            "╔═════╗\n║     ║\n║  a  ║\n║     ║\n╚═════╝"
+        );
+        assert_eq!(
+            Panel::new("a")
+                .title("Title")
+                .title_style(style!())
+                .border(Border::Heavy)
+                .to_string(),
+            "┏━ Title ━┓\n┃ a       ┃\n┗━━━━━━━━━┛"
+        );
+        assert_eq!(
+            Panel::new("a\nb")
+                .title("T")
+                .title_style(style!())
+                .border(Border::None)
+                .padding(0, 0)
+                .to_string(),
+            "T\na\nb"
+        );
+    }
+
+    #[test]
+    fn panel_works_w_styles() {
+        let mut title = StyledString::new();
+        title.push("T", style!(Red));
+
+        assert_eq!(
+            Panel::new(stylize!("ab": Green))
+                .title(title)
+                .border(Border::Rounded)
+                .border_style(style!(Blue))
+                .to_string(),
+            "\x1b[34m╭─ \x1b[0m\x1b[31;1mT\x1b[0m\x1b[34m ─╮\x1b[0m\n\
+            \x1b[34m│\x1b[0m \x1b[32mab\x1b[0m  \x1b[34m│\x1b[0m\n\
+            \x1b[34m╰─────╯\x1b[0m"
+        );
+
+        // Title style is applied again after resets in the title
+        assert_eq!(
+            Panel::new("a")
+                .title(stylize!("T": Red) + "x")
+                .border(Border::None)
+                .to_string(),
+            "\x1b[1m\x1b[31mT\x1b[0m\x1b[1mx\x1b[0m\n a"
+        );
+    }
+}
@@ -0,0 +1,571 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::{Color, Style};
+
+/// Error from parsing a `Style` or `Color` from a string
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseStyleError {
+    /// Name is not a color
+    UnknownColor(String),
+    /// Name is not a color or decoration
+    UnknownName(String),
+    /// SGR parameters cannot be represented as a `Style`
+    InvalidSgr(String),
+    /// Git color value has more than two colors
+    InvalidGitColor(String),
+}
+
+impl fmt::Display for ParseStyleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownColor(name) => write!(f, "unknown color `{name}`"),
+            Self::UnknownName(name) => write!(f, "unknown color or decoration `{name}`"),
+            Self::InvalidSgr(params) => write!(f, "invalid SGR parameters `{params}`"),
+            Self::InvalidGitColor(value) => write!(f, "too many colors in `{value}`"),
+        }
+    }
+}
+
+impl Error for ParseStyleError {}
+
+/// Write a color name, a 256-color palette index, or a hex color
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Color::Fixed(index) => write!(f, "{index}"),
+            Color::Rgb(r, g, b) => write!(f, "#{r:02x}{g:02x}{b:02x}"),
+            _ => write!(f, "{}", self.name().unwrap_or_default()),
+        }
+    }
+}
+
+/// Parse a color name (ignoring case), a 256-color palette index, or a hex color
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::Color::{self, *};
+/// assert_eq!("red".parse(), Ok(Red));
+/// assert_eq!("208".parse(), Ok(Fixed(208)));
+/// assert_eq!("#ff8700".parse(), Ok(Rgb(255, 135, 0)));
+/// ```
+impl FromStr for Color {
+    type Err = ParseStyleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let error = || ParseStyleError::UnknownColor(s.to_string());
+
+        if let Some(hex) = s.strip_prefix('#') {
+            if hex.len() != 6 || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+                return Err(error());
+            }
+            let channel = |i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| error());
+            return Ok(Color::Rgb(channel(0)?, channel(2)?, channel(4)?));
+        }
+
+        if s.starts_with(|ch: char| ch.is_ascii_digit()) {
+            return s.parse().map(Color::Fixed).map_err(|_| error());
+        }
+
+        Color::from_name(s).ok_or_else(error)
+    }
+}
+
+impl Style {
+    /// Get a decoration field from its long name
+    fn decoration_mut(&mut self, name: &str) -> Option<&mut bool> {
+        Some(match name {
+            "bold" => &mut self.bold,
+            "dim" => &mut self.dim,
+            "italic" => &mut self.italic,
+            "underline" => &mut self.underline,
+            "blink" => &mut self.blink,
+            "reverse" => &mut self.reverse,
+            "strikethrough" => &mut self.strikethrough,
+            _ => return None,
+        })
+    }
+
+    /// Parse a `Style` from SGR parameters, as used in ANSI codes and `LS_COLORS`
+    ///
+    /// Parameters are separated with `;`, such as `01;34` or `38;5;208`
+    ///
+    /// Bright colors (`90`-`97`) are converted to `Fixed` colors
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stilo::{style, Style, Color::*};
+    /// assert_eq!(Style::from_sgr("01;34"), Ok(style!(Blue + bold)));
+    /// assert_eq!(Style::from_sgr("38;5;208"), Ok(Style::new().color(Fixed(208))));
+    /// assert_eq!(Style::from_sgr("30;42"), Ok(style!(Black).background(Green)));
+    /// ```
+    pub fn from_sgr(params: &str) -> Result<Self, ParseStyleError> {
+        match Self::parse_sgr(params) {
+            (style, true) => Ok(style),
+            (_, false) => Err(ParseStyleError::InvalidSgr(params.to_string())),
+        }
+    }
+
+    /// Parse a `Style` from SGR parameters, ignoring any codes which cannot be represented
+    pub(crate) fn from_sgr_lossy(params: &str) -> Self {
+        Self::parse_sgr(params).0
+    }
+
+    /// Parse SGR parameters, skipping invalid codes
+    ///
+    /// Returns the `Style`, and `false` if any codes were skipped
+    fn parse_sgr(params: &str) -> (Self, bool) {
+        let mut valid = true;
+        let mut codes = params.split(';').map(|code| match code.trim() {
+            "" => Some(0),
+            code => code.parse::<u8>().ok(),
+        });
+
+        let mut style = Style::new();
+
+        while let Some(code) = codes.next() {
+            let Some(code) = code else {
+                valid = false;
+                continue;
+            };
+
+            style = match code {
+                0 => Style::new(),
+                1 => style.bold(),
+                2 => style.dim(),
+                3 => style.italic(),
+                4 => style.underline(),
+                5 => style.blink(),
+                7 => style.reverse(),
+                9 => style.strikethrough(),
+                22 => Style {
+                    bold: false,
+                    dim: false,
+                    ..style
+                },
+                23 => Style {
+                    italic: false,
+                    ..style
+                },
+                24 => Style {
+                    underline: false,
+                    ..style
+                },
+                25 => Style {
+                    blink: false,
+                    ..style
+                },
+                27 => Style {
+                    reverse: false,
+                    ..style
+                },
+                29 => Style {
+                    strikethrough: false,
+                    ..style
+                },
+                30..=37 => match Color::from_param(code) {
+                    Some(color) => style.color(color),
+                    None => style,
+                },
+                40..=47 => match Color::from_param(code - 10) {
+                    Some(color) => style.background(color),
+                    None => style,
+                },
+                90..=97 => style.color(Color::Fixed(code - 90 + 8)),
+                100..=107 => style.background(Color::Fixed(code - 100 + 8)),
+                39 => Style {
+                    color: None,
+                    ..style
+                },
+                49 => Style {
+                    background: None,
+                    ..style
+                },
+                38 | 48 => {
+                    let mut next = || codes.next().flatten();
+                    let color = match next() {
+                        Some(5) => next().map(Color::Fixed),
+                        Some(2) => match (next(), next(), next()) {
+                            (Some(r), Some(g), Some(b)) => Some(Color::Rgb(r, g, b)),
+                            _ => None,
+                        },
+                        _ => None,
+                    };
+                    match color {
+                        Some(color) if code == 38 => style.color(color),
+                        Some(color) => style.background(color),
+                        None => {
+                            valid = false;
+                            style
+                        }
+                    }
+                }
+                _ => {
+                    valid = false;
+                    style
+                }
+            };
+        }
+
+        (style, valid)
+    }
+
+    /// Parse a `Style` from a git color value, such as `bold red blue` or `#ff0000 ul`
+    ///
+    /// The first color is the foreground, and the second is the background.
+    /// Attributes can be negated with `no` or `no-`, such as `nobold`.
+    ///
+    /// Bright colors (such as `brightred`) are converted to `Fixed` colors
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stilo::{style, Style, Color::*};
+    /// assert_eq!(Style::from_git("bold red blue"), Ok(style!(Red + bold).background(Blue)));
+    /// assert_eq!(Style::from_git("#ff0000 ul"), Ok(style!(+u).color(Rgb(255, 0, 0))));
+    /// assert_eq!(Style::from_git("reverse"), Ok(style!(+reverse)));
+    /// ```
+    pub fn from_git(value: &str) -> Result<Self, ParseStyleError> {
+        let mut style = Style::new();
+        let mut colors = 0;
+
+        for word in value.split_whitespace() {
+            let word = word.to_ascii_lowercase();
+
+            if let Some(color) = git_color(&word) {
+                match colors {
+                    0 => style.color = color,
+                    1 => style.background = color,
+                    _ => return Err(ParseStyleError::InvalidGitColor(value.to_string())),
+                }
+                colors += 1;
+                continue;
+            }
+
+            let (negate, name) = match word.strip_prefix("no") {
+                Some(name) => (true, name.strip_prefix('-').unwrap_or(name)),
+                None => (false, word.as_str()),
+            };
+            let name = match name {
+                "reset" if !negate => continue,
+                "ul" => "underline",
+                "strike" => "strikethrough",
+                name => name,
+            };
+
+            match style.decoration_mut(name) {
+                Some(decoration) => *decoration = !negate,
+                None => return Err(ParseStyleError::UnknownName(word)),
+            }
+        }
+
+        Ok(style)
+    }
+}
+
+/// Parse a git color name, number, or hex color
+///
+/// Returns `Some(None)` for `normal` and `default`, and `None` if the word is not a color
+fn git_color(word: &str) -> Option<Option<Color>> {
+    if let Some(hex) = word.strip_prefix('#') {
+        // Expand short form, such as `#f00`
+        if hex.len() == 3 {
+            let hex: String = hex.chars().flat_map(|ch| [ch, ch]).collect();
+            return format!("#{hex}").parse().ok().map(Some);
+        }
+        return word.parse().ok().map(Some);
+    }
+
+    match word {
+        "normal" | "default" => return Some(None),
+        _ => (),
+    }
+
+    if let Ok(index) = word.parse() {
+        return Some(Some(Color::Fixed(index)));
+    }
+
+    match word.strip_prefix("bright") {
+        // Bright colors follow the standard colors in the 256-color palette
+        Some(name) => Color::from_name(name).and_then(|color| {
+            (0..8)
+                .find(|index| Color::from_param(30 + index) == Some(color))
+                .map(|index| Some(Color::Fixed(index + 8)))
+        }),
+        None => Color::from_name(word).map(Some),
+    }
+}
+
+/// Write a `Style` in the same syntax as the `style!` macro
+///
+/// Long decoration names are always used, and a background color is written as `on <color>`
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::style;
+/// assert_eq!(style!(Red + i+b).to_string(), "Red+bold+italic");
+/// assert_eq!(style!(+u).to_string(), "+underline");
+/// assert_eq!(style!().to_string(), "");
+/// ```
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(color) = self.color {
+            write!(f, "{color}")?;
+        }
+        if let Some(background) = self.background {
+            if self.color.is_some() {
+                write!(f, " ")?;
+            }
+            write!(f, "on {background}")?;
+        }
+
+        /// Private macro for writing decoration names
+        macro_rules! decor_name {
+            ( $( $name: ident ),* $(,)? ) => {
+                $(
+                    if self.$name {
+                        write!(f, concat!("+", stringify!($name)))?;
+                    }
+                )*
+            };
+        }
+
+        decor_name!(bold, dim, italic, underline, blink, reverse, strikethrough);
+
+        Ok(())
+    }
+}
+
+/// Parse a `Style` from the same syntax as the `style!` macro
+///
+/// Color names ignore case, and decorations may be written with or without a leading `+`
+///
+/// A background color is written as `on <color>`, optionally after the foreground color
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{style, Color::*};
+/// assert_eq!("Red + i+b".parse(), Ok(style!(Red + italic + bold)));
+/// assert_eq!("+underline".parse(), Ok(style!(+u)));
+/// assert_eq!("bold+red".parse(), Ok(style!(Red + b)));
+/// assert_eq!("Red on Blue + b".parse(), Ok(style!(Red + b).background(Blue)));
+/// ```
+impl FromStr for Style {
+    type Err = ParseStyleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut style = Style::new();
+
+        for (i, name) in s.split('+').map(str::trim).enumerate() {
+            if name.is_empty() {
+                // Allow leading `+`, or empty string
+                if i == 0 {
+                    continue;
+                }
+                return Err(ParseStyleError::UnknownName(name.to_string()));
+            }
+
+            let decoration = match name {
+                "b" => "bold",
+                "d" => "dim",
+                "i" => "italic",
+                "u" => "underline",
+                "r" => "reverse",
+                "s" => "strikethrough",
+                name => name,
+            };
+
+            style = match style.decoration_mut(decoration) {
+                Some(decoration) => {
+                    *decoration = true;
+                    style
+                }
+                None => {
+                    let (color, background) = match name.strip_prefix("on ") {
+                        Some(background) => ("", Some(background)),
+                        None => match name.split_once(" on ") {
+                            Some((color, background)) => (color.trim(), Some(background)),
+                            None => (name, None),
+                        },
+                    };
+
+                    if let Some(background) = background {
+                        style = style.background(background.parse()?);
+                    }
+                    match color {
+                        "" => style,
+                        _ => match color.parse() {
+                            Ok(color) => style.color(color),
+                            Err(_) => return Err(ParseStyleError::UnknownName(name.to_string())),
+                        },
+                    }
+                }
+            };
+        }
+
+        Ok(style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{style, Color::*};
+
+    #[test]
+    fn color_parse_works() {
+        assert_eq!("Red".parse(), Ok(Red));
+        assert_eq!("magenta".parse(), Ok(Magenta));
+        assert_eq!(" CYAN ".parse(), Ok(Cyan));
+        assert_eq!(
+            "Purple".parse::<Color>(),
+            Err(ParseStyleError::UnknownColor("Purple".into()))
+        );
+
+        assert_eq!("0".parse(), Ok(Fixed(0)));
+        assert_eq!("208".parse(), Ok(Fixed(208)));
+        assert_eq!("#FF8700".parse(), Ok(Rgb(255, 135, 0)));
+        assert!("256".parse::<Color>().is_err());
+        assert!("#ff87".parse::<Color>().is_err());
+        assert!("#gg8700".parse::<Color>().is_err());
+        assert!("#+f+f+f".parse::<Color>().is_err());
+
+        assert_eq!(Red.to_string(), "Red");
+        assert_eq!(White.to_string(), "White");
+        assert_eq!(Fixed(208).to_string(), "208");
+        assert_eq!(Rgb(255, 135, 0).to_string(), "#ff8700");
+    }
+
+    #[test]
+    fn sgr_parse_works() {
+        let parse = |params| Style::from_sgr(params).unwrap();
+
+        assert_eq!(parse(""), style!());
+        assert_eq!(parse("0"), style!());
+        assert_eq!(parse("00"), style!());
+        assert_eq!(parse("34"), style!(Blue));
+        assert_eq!(parse("01;34"), style!(Blue + bold));
+        assert_eq!(parse("1;2;3;4"), style!(+b+d+i+u));
+        assert_eq!(parse("01;31;00;32"), style!(Green));
+        assert_eq!(parse("31;39"), style!());
+        assert_eq!(parse("40;31;01"), style!(Red + bold).background(Black));
+        assert_eq!(parse("41;49"), style!());
+        assert_eq!(parse("92"), style!().color(Fixed(10)));
+        assert_eq!(parse("107"), style!().background(Fixed(15)));
+        assert_eq!(parse("38;5;208"), style!().color(Fixed(208)));
+        assert_eq!(parse("48;5;16;1"), style!(+b).background(Fixed(16)));
+        assert_eq!(parse("38;2;255;135;0"), style!().color(Rgb(255, 135, 0)));
+
+        assert_eq!(parse("5;7;9"), style!(+blink+r+s));
+        assert_eq!(parse("1;2;3;22;23"), style!());
+        assert_eq!(parse("4;5;7;9;24;25;27;29"), style!());
+
+        assert!(Style::from_sgr("38;5").is_err());
+        assert!(Style::from_sgr("38;2;255;135").is_err());
+        assert!(Style::from_sgr("38;7;1").is_err());
+        assert!(Style::from_sgr("256").is_err());
+        assert!(Style::from_sgr("bold").is_err());
+
+        assert_eq!(Style::from_sgr_lossy("01;34;53"), style!(Blue + bold));
+        assert_eq!(Style::from_sgr_lossy("x;4;53;31"), style!(Red + u));
+        assert_eq!(Style::from_sgr_lossy("38;5"), style!());
+    }
+
+    #[test]
+    fn git_parse_works() {
+        let parse = |value| Style::from_git(value).unwrap();
+
+        assert_eq!(parse(""), style!());
+        assert_eq!(parse("red"), style!(Red));
+        assert_eq!(parse("bold red"), style!(Red + bold));
+        assert_eq!(parse("red bold"), style!(Red + bold));
+        assert_eq!(parse("bold red blue"), style!(Red + bold).background(Blue));
+        assert_eq!(parse("normal blue"), style!().background(Blue));
+        assert_eq!(parse("default ul"), style!(+u));
+        assert_eq!(parse("Yellow Reverse"), style!(Yellow + reverse));
+        assert_eq!(parse("reverse"), style!(+r));
+        assert_eq!(parse("dim italic blink strike"), style!(+d+i+blink+s));
+        assert_eq!(parse("bold nobold"), style!());
+        assert_eq!(parse("ul no-ul italic"), style!(+i));
+        assert_eq!(parse("reset green"), style!(Green));
+        assert_eq!(parse("brightred"), style!().color(Fixed(9)));
+        assert_eq!(
+            parse("208 0"),
+            style!().color(Fixed(208)).background(Fixed(0))
+        );
+        assert_eq!(parse("#ff0000 ul"), style!(+u).color(Rgb(255, 0, 0)));
+        assert_eq!(parse("#f0a"), style!().color(Rgb(255, 0, 170)));
+
+        assert_eq!(
+            Style::from_git("red blue green"),
+            Err(ParseStyleError::InvalidGitColor("red blue green".into()))
+        );
+        assert_eq!(
+            Style::from_git("red heavy"),
+            Err(ParseStyleError::UnknownName("heavy".into()))
+        );
+        assert!(Style::from_git("#ff00").is_err());
+        assert!(Style::from_git("256").is_err());
+        assert!(Style::from_git("noreset").is_err());
+    }
+
+    #[test]
+    fn style_parse_works() {
+        assert_eq!("".parse(), Ok(style!()));
+        assert_eq!("Red".parse(), Ok(style!(Red)));
+        assert_eq!("Red+bold".parse(), Ok(style!(Red + bold)));
+        assert_eq!("Blue + italic+b".parse(), Ok(style!(Blue + italic + b)));
+        assert_eq!("+u+d+bold".parse(), Ok(style!(+u+d+bold)));
+        assert_eq!("italic".parse(), Ok(style!(+i)));
+        assert_eq!(
+            "+blink+r+s".parse(),
+            Ok(style!(+blink+reverse+strikethrough))
+        );
+        assert_eq!("green+bold".parse(), Ok(style!(Green + b)));
+        assert_eq!("Red+Blue".parse(), Ok(style!(Blue)));
+        assert_eq!("208+b".parse(), Ok(style!(+b).color(Fixed(208))));
+        assert_eq!("Red on Blue".parse(), Ok(style!(Red).background(Blue)));
+        assert_eq!(
+            "on #000000+i".parse(),
+            Ok(style!(+i).background(Rgb(0, 0, 0)))
+        );
+
+        assert_eq!(
+            "Red+heavy".parse::<Style>(),
+            Err(ParseStyleError::UnknownName("heavy".into()))
+        );
+        assert_eq!(
+            "Red++bold".parse::<Style>(),
+            Err(ParseStyleError::UnknownName("".into()))
+        );
+    }
+
+    #[test]
+    fn style_display_round_trips() {
+        for style in [
+            style!(),
+            style!(Red),
+            style!(Red + bold),
+            style!(+i+u),
+            style!(Yellow + b + d + i + u),
+            style!(+blink+r+s),
+            style!(+u).background(Cyan),
+            style!(Red + b).background(Fixed(4)),
+            style!().color(Rgb(1, 2, 3)),
+        ] {
+            assert_eq!(style.to_string().parse(), Ok(style));
+        }
+
+        assert_eq!(style!(Green + i + b).to_string(), "Green+bold+italic");
+        assert_eq!(style!(+d).to_string(), "+dim");
+        assert_eq!(
+            style!(Red + b).background(Blue).to_string(),
+            "Red on Blue+bold"
+        );
+        assert_eq!(style!().background(Fixed(4)).to_string(), "on 4");
+    }
+}
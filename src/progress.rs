@@ -0,0 +1,538 @@
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+use crate::{strip_ansi, Color::*, Stream, Style, StyledString};
+
+/// Default spinner frames
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Progress bar, or spinner if the total is unknown
+///
+/// On a terminal, the bar is drawn again in place. Otherwise, plain lines are written
+/// periodically, and when finished.
+///
+/// Lines are not cut to the width of the terminal, so each line should fit in the terminal.
+/// A line which wraps is not cleared fully when drawn again.
+///
+/// The template can contain these placeholders:
+/// - `{bar}`: bar of filled and empty characters
+/// - `{spinner}`: spinner frame
+/// - `{percent}`: percentage done
+/// - `{pos}` and `{total}`: position and total
+/// - `{elapsed}`: time since the start
+/// - `{eta}`: estimated time left
+/// - `{rate}`: steps per second
+/// - `{msg}`: message
+///
+/// A space next to an empty placeholder, such as `{msg}` with no message, is removed.
+///
+/// Create with `ProgressBar::new(writer, total)` or `ProgressBar::stderr(total)`
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{style, ProgressBar};
+/// let mut bar = ProgressBar::stderr(100)
+///     .template("{msg} [{bar}] {percent}% ETA {eta}")
+///     .chars('#', '-')
+///     .filled_style(style!(Green))
+///     .message("Building");
+///
+/// for _ in 0..100 {
+///     bar.inc(1).unwrap();
+/// }
+/// bar.finish_with_message("Done").unwrap();
+///
+/// let mut spinner = ProgressBar::stderr_spinner().message("Waiting");
+/// spinner.tick().unwrap();
+/// spinner.finish().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ProgressBar<W: Write> {
+    writer: W,
+    terminal: bool,
+    color: bool,
+    total: Option<u64>,
+    position: u64,
+    message: String,
+    template: String,
+    width: usize,
+    filled: char,
+    empty: char,
+    filled_style: Style,
+    empty_style: Style,
+    spinner_style: Style,
+    spinner_frames: Vec<String>,
+    frame: usize,
+    interval: Option<Duration>,
+    start: Instant,
+    last_draw: Option<Instant>,
+    lines: usize,
+}
+
+impl<W: Write> ProgressBar<W> {
+    /// Create a `ProgressBar` with a total number of steps
+    ///
+    /// Writes plain lines with no color, until `terminal` and `color` are set
+    pub fn new(writer: W, total: u64) -> Self {
+        Self {
+            writer,
+            terminal: false,
+            color: false,
+            total: Some(total),
+            position: 0,
+            message: String::new(),
+            template: "{msg} {bar} {percent}% ({pos}/{total}, ETA {eta})".into(),
+            width: 30,
+            filled: '█',
+            empty: '░',
+            filled_style: Style::new().color(Cyan),
+            empty_style: Style::new().dim(),
+            spinner_style: Style::new().color(Cyan),
+            spinner_frames: SPINNER_FRAMES
+                .iter()
+                .map(|frame| frame.to_string())
+                .collect(),
+            frame: 0,
+            interval: None,
+            start: Instant::now(),
+            last_draw: None,
+            lines: 0,
+        }
+    }
+
+    /// Create a spinner, with an unknown total
+    ///
+    /// Writes plain lines with no color, until `terminal` and `color` are set
+    pub fn new_spinner(writer: W) -> Self {
+        Self {
+            total: None,
+            template: "{spinner} {msg} ({elapsed})".into(),
+            ..Self::new(writer, 0)
+        }
+    }
+
+    /// Draw again in place, for a terminal
+    ///
+    /// Each line should fit in the terminal, since lines which wrap are not cleared fully
+    pub fn terminal(mut self, terminal: bool) -> Self {
+        self.terminal = terminal;
+        self
+    }
+
+    /// Enable or disable color
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the template, with placeholders such as `{bar}`
+    ///
+    /// Lines are separated with newlines
+    pub fn template(mut self, template: impl Into<String>) -> Self {
+        self.template = template.into();
+        self
+    }
+
+    /// Set the number of characters in the bar
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the characters for the filled and empty parts of the bar
+    pub fn chars(mut self, filled: char, empty: char) -> Self {
+        self.filled = filled;
+        self.empty = empty;
+        self
+    }
+
+    /// Add a `Style` for the filled part of the bar
+    pub fn filled_style(mut self, style: Style) -> Self {
+        self.filled_style = style;
+        self
+    }
+
+    /// Add a `Style` for the empty part of the bar
+    pub fn empty_style(mut self, style: Style) -> Self {
+        self.empty_style = style;
+        self
+    }
+
+    /// Add a `Style` for the spinner
+    pub fn spinner_style(mut self, style: Style) -> Self {
+        self.spinner_style = style;
+        self
+    }
+
+    /// Set the frames of the spinner
+    pub fn spinner_frames<T: Into<String>>(mut self, frames: impl IntoIterator<Item = T>) -> Self {
+        self.spinner_frames = frames.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the message
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Set the shortest time between draws
+    ///
+    /// Defaults to 100 milliseconds on a terminal, and 5 seconds for plain lines
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Get the position
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Move forward a number of steps, and draw if needed
+    pub fn inc(&mut self, steps: u64) -> io::Result<()> {
+        self.set_position(self.position.saturating_add(steps))
+    }
+
+    /// Set the position, and draw if needed
+    ///
+    /// Position is limited to the total
+    pub fn set_position(&mut self, position: u64) -> io::Result<()> {
+        self.position = match self.total {
+            Some(total) => position.min(total),
+            None => position,
+        };
+        self.draw(false)
+    }
+
+    /// Change the message, and draw if needed
+    pub fn set_message(&mut self, message: impl Into<String>) -> io::Result<()> {
+        self.message = message.into();
+        self.draw(false)
+    }
+
+    /// Move the spinner forward, and draw if needed
+    pub fn tick(&mut self) -> io::Result<()> {
+        self.frame = self.frame.wrapping_add(1);
+        self.draw(false)
+    }
+
+    /// Fill the bar and draw for the last time
+    ///
+    /// On a terminal, the cursor is moved to the next line
+    pub fn finish(&mut self) -> io::Result<()> {
+        if let Some(total) = self.total {
+            self.position = total;
+        }
+        self.draw(true)?;
+        if self.terminal {
+            writeln!(self.writer)?;
+        }
+        self.writer.flush()
+    }
+
+    /// Change the message, then finish
+    pub fn finish_with_message(&mut self, message: impl Into<String>) -> io::Result<()> {
+        self.message = message.into();
+        self.finish()
+    }
+
+    /// Get a reference to the underlying writer
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Get the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Draw, unless the last draw was too recent
+    fn draw(&mut self, force: bool) -> io::Result<()> {
+        let now = Instant::now();
+        let interval = self.interval.unwrap_or(if self.terminal {
+            Duration::from_millis(100)
+        } else {
+            Duration::from_secs(5)
+        });
+        if let Some(last_draw) = self.last_draw {
+            if !force && now.duration_since(last_draw) < interval {
+                return Ok(());
+            }
+        }
+        self.last_draw = Some(now);
+
+        let mut text = self.render(now.duration_since(self.start));
+        if !self.color {
+            text = strip_ansi(&text);
+        }
+
+        if self.terminal {
+            // Clear the lines of the last draw
+            if self.lines > 0 {
+                write!(self.writer, "\r\x1b[2K")?;
+                for _ in 1..self.lines {
+                    write!(self.writer, "\x1b[1A\x1b[2K")?;
+                }
+            }
+            // Not `lines`, which ignores a newline at the end
+            self.lines = text.matches('\n').count() + 1;
+            write!(self.writer, "{text}")?;
+        } else {
+            writeln!(self.writer, "{text}")?;
+        }
+        self.writer.flush()
+    }
+
+    /// Replace placeholders in the template
+    fn render(&self, elapsed: Duration) -> String {
+        let mut output = String::new();
+        let mut rest = self.template.as_str();
+
+        while let Some(start) = rest.find('{') {
+            output += &rest[..start];
+            rest = &rest[start..];
+
+            let Some(end) = rest.find('}') else {
+                break;
+            };
+            let (placeholder, name) = (&rest[..=end], &rest[1..end]);
+            rest = &rest[end + 1..];
+            match self.placeholder(name, elapsed) {
+                // Remove a space next to an empty value, so the spaces around it do not double
+                Some(value) if value.is_empty() => {
+                    let line_start = output.is_empty() || output.ends_with('\n');
+                    if rest.starts_with(' ') && (line_start || output.ends_with(' ')) {
+                        rest = &rest[1..];
+                    } else if output.ends_with(' ') && (rest.is_empty() || rest.starts_with('\n')) {
+                        output.pop();
+                    }
+                }
+                Some(value) => output += &value,
+                None => output += placeholder,
+            }
+        }
+
+        output + rest
+    }
+
+    /// Get the value of a placeholder, or `None` if not known
+    fn placeholder(&self, name: &str, elapsed: Duration) -> Option<String> {
+        let unknown = || "?".to_string();
+        let total = self.total;
+        // Fraction done, if the total is known
+        let fraction = total.map(|total| match total {
+            0 => 1.0,
+            _ => self.position as f64 / total as f64,
+        });
+
+        Some(match name {
+            "bar" => {
+                let filled = (fraction.unwrap_or(0.0) * self.width as f64) as usize;
+                let mut bar = StyledString::new();
+                bar.push(self.filled.to_string().repeat(filled), self.filled_style);
+                bar.push(
+                    self.empty.to_string().repeat(self.width - filled),
+                    self.empty_style,
+                );
+                bar.to_string()
+            }
+            "spinner" => match self.spinner_frames.len() {
+                0 => String::new(),
+                len => self
+                    .spinner_style
+                    .format(&self.spinner_frames[self.frame % len]),
+            },
+            "percent" => {
+                fraction.map_or_else(unknown, |fraction| format!("{}", (fraction * 100.0) as u64))
+            }
+            "pos" => self.position.to_string(),
+            "total" => total.map_or_else(unknown, |total| total.to_string()),
+            "elapsed" => format_duration(elapsed),
+            "eta" => match total {
+                Some(total) if self.position > 0 => {
+                    let left = (total - self.position) as f64 / self.position as f64;
+                    format_duration(elapsed.mul_f64(left))
+                }
+                _ => unknown(),
+            },
+            "rate" => {
+                let secs = elapsed.as_secs_f64();
+                if secs <= 0.0 {
+                    unknown()
+                } else {
+                    format!("{:.1}/s", self.position as f64 / secs)
+                }
+            }
+            "msg" => self.message.clone(),
+            _ => return None,
+        })
+    }
+}
+
+impl ProgressBar<io::Stderr> {
+    /// Create a `ProgressBar` for stderr, drawn in place if stderr is a terminal
+    ///
+    /// Has color if enabled for stderr. See `Stream::color_enabled`
+    pub fn stderr(total: u64) -> Self {
+        Self::new(io::stderr(), total)
+            .terminal(Stream::Stderr.is_terminal())
+            .color(Stream::Stderr.color_enabled())
+    }
+
+    /// Create a spinner for stderr, drawn in place if stderr is a terminal
+    ///
+    /// Has color if enabled for stderr. See `Stream::color_enabled`
+    pub fn stderr_spinner() -> Self {
+        Self::new_spinner(io::stderr())
+            .terminal(Stream::Stderr.is_terminal())
+            .color(Stream::Stderr.color_enabled())
+    }
+}
+
+/// Format a duration as seconds, minutes, and hours, such as `1m05s`
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match (secs / 3600, secs / 60 % 60, secs % 60) {
+        (0, 0, s) => format!("{s}s"),
+        (0, m, s) => format!("{m}m{s:02}s"),
+        (h, m, s) => format!("{h}h{m:02}m{s:02}s"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style;
+
+    fn output(bar: ProgressBar<Vec<u8>>) -> String {
+        String::from_utf8(bar.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn render_works() {
+        let mut bar = ProgressBar::new(Vec::new(), 40)
+            .template("{msg} [{bar}] {percent}% {pos}/{total} {eta} {rate} {elapsed} {x}")
+            .width(8)
+            .chars('#', '.')
+            .filled_style(style!(Green))
+            .empty_style(style!())
+            .message("Building");
+
+        assert_eq!(
+            bar.render(Duration::ZERO),
+            "Building [........] 0% 0/40 ? ? 0s {x}"
+        );
+
+        bar.position = 10;
+        assert_eq!(
+            bar.render(Duration::from_secs(20)),
+            "Building [\x1b[32m##\x1b[0m......] 25% 10/40 1m00s 0.5/s 20s {x}"
+        );
+
+        bar.position = 40;
+        bar.message.clear();
+        assert_eq!(
+            bar.render(Duration::from_secs(3725)),
+            "[\x1b[32m########\x1b[0m] 100% 40/40 0s 0.0/s 1h02m05s {x}"
+        );
+
+        let bar = ProgressBar::new(Vec::new(), 0).template("{percent}%\n {bar} {");
+        assert_eq!(
+            strip_ansi(&bar.render(Duration::ZERO)),
+            "100%\n ██████████████████████████████ {"
+        );
+    }
+
+    #[test]
+    fn render_works_w_empty_placeholders() {
+        let bar = ProgressBar::new(Vec::new(), 1).template("{msg} a  {msg} b {msg}\n{msg} c {msg}");
+        assert_eq!(bar.render(Duration::ZERO), "a  b\nc");
+
+        // Spaces in the template are kept
+        let bar = ProgressBar::new(Vec::new(), 1)
+            .template("  {pos}/{total} ")
+            .message("x");
+        assert_eq!(bar.render(Duration::ZERO), "  0/1 ");
+    }
+
+    #[test]
+    fn render_works_w_spinner() {
+        let mut spinner = ProgressBar::new_spinner(Vec::new())
+            .spinner_frames(["-", "\\", "|", "/"])
+            .spinner_style(style!())
+            .message("Waiting");
+
+        assert_eq!(spinner.render(Duration::from_secs(61)), "- Waiting (1m01s)");
+        spinner.frame = 5;
+        assert_eq!(spinner.render(Duration::ZERO), "\\ Waiting (0s)");
+
+        let spinner = spinner.template("{percent} {total} {eta} {pos}");
+        assert_eq!(spinner.render(Duration::ZERO), "? ? ? 0");
+    }
+
+    #[test]
+    fn draw_works_w_plain_lines() {
+        let mut bar = ProgressBar::new(Vec::new(), 4)
+            .template("{bar} {pos}")
+            .width(4)
+            .color(true)
+            .interval(Duration::from_secs(3600));
+
+        bar.inc(1).unwrap();
+        bar.inc(1).unwrap();
+        bar.set_message("a").unwrap();
+        bar.finish().unwrap();
+
+        // Only the first draw and the last are written, before the interval
+        assert_eq!(strip_ansi(&output(bar)), "█░░░ 1\n████ 4\n");
+
+        let mut bar = ProgressBar::new(Vec::new(), 2)
+            .template("{bar}")
+            .width(2)
+            .interval(Duration::ZERO);
+        bar.inc(1).unwrap();
+        bar.inc(5).unwrap();
+        assert_eq!(bar.position(), 2);
+        assert_eq!(output(bar), "█░\n██\n");
+    }
+
+    #[test]
+    fn draw_works_w_terminal() {
+        let mut bar = ProgressBar::new(Vec::new(), 2)
+            .template("{msg}\n{pos}")
+            .terminal(true)
+            .interval(Duration::ZERO);
+
+        bar.set_message("a").unwrap();
+        bar.inc(1).unwrap();
+        bar.finish().unwrap();
+        assert_eq!(
+            output(bar),
+            "a\n0\r\x1b[2K\x1b[1A\x1b[2Ka\n1\r\x1b[2K\x1b[1A\x1b[2Ka\n2\n"
+        );
+
+        let mut spinner = ProgressBar::new_spinner(Vec::new())
+            .template("{spinner}")
+            .spinner_frames(["a", "b"])
+            .terminal(true)
+            .color(true)
+            .spinner_style(style!(Red))
+            .interval(Duration::ZERO);
+
+        spinner.tick().unwrap();
+        spinner.tick().unwrap();
+        assert_eq!(output(spinner), "\x1b[31mb\x1b[0m\r\x1b[2K\x1b[31ma\x1b[0m");
+        // Newline at the end of the template
+        let mut bar = ProgressBar::new(Vec::new(), 2)
+            .template("{pos}\n")
+            .terminal(true)
+            .interval(Duration::ZERO);
+        bar.inc(1).unwrap();
+        bar.inc(1).unwrap();
+        assert_eq!(output(bar), "1\n\r\x1b[2K\x1b[1A\x1b[2K2\n");
+    }
+}
@@ -0,0 +1,191 @@
+use std::fmt;
+
+use serde::{
+    de::{self, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{Color, Style};
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Serialize as the same syntax as the `style!` macro, such as `"Red+bold"`
+///
+/// Formats which are not human-readable, such as `bincode`, use the structured form
+impl Serialize for Style {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            StyleFields::from(*self).serialize(serializer)
+        }
+    }
+}
+
+/// Deserialize from the same syntax as the `style!` macro, such as `"Red+bold"`
+///
+/// A structured form is also accepted, such as `{ color = "Red", bold = true }`
+///
+/// Formats which are not human-readable, such as `bincode`, only use the structured form
+impl<'de> Deserialize<'de> for Style {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(StyleVisitor)
+        } else {
+            StyleFields::deserialize(deserializer).map(Style::from)
+        }
+    }
+}
+
+/// Structured form of `Style`, with all fields optional
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StyleFields {
+    #[serde(default)]
+    color: Option<Color>,
+    #[serde(default)]
+    background: Option<Color>,
+    #[serde(default)]
+    bold: bool,
+    #[serde(default)]
+    dim: bool,
+    #[serde(default)]
+    italic: bool,
+    #[serde(default)]
+    underline: bool,
+    #[serde(default)]
+    blink: bool,
+    #[serde(default)]
+    reverse: bool,
+    #[serde(default)]
+    strikethrough: bool,
+}
+
+impl From<Style> for StyleFields {
+    fn from(style: Style) -> Self {
+        Self {
+            color: style.color,
+            background: style.background,
+            bold: style.bold,
+            dim: style.dim,
+            italic: style.italic,
+            underline: style.underline,
+            blink: style.blink,
+            reverse: style.reverse,
+            strikethrough: style.strikethrough,
+        }
+    }
+}
+
+impl From<StyleFields> for Style {
+    fn from(fields: StyleFields) -> Self {
+        Self {
+            color: fields.color,
+            background: fields.background,
+            bold: fields.bold,
+            dim: fields.dim,
+            italic: fields.italic,
+            underline: fields.underline,
+            blink: fields.blink,
+            reverse: fields.reverse,
+            strikethrough: fields.strikethrough,
+        }
+    }
+}
+
+struct StyleVisitor;
+
+impl<'de> Visitor<'de> for StyleVisitor {
+    type Value = Style;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "a style string such as \"Red+bold\", or a map of style fields"
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Style, E> {
+        value.parse().map_err(E::custom)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Style, A::Error> {
+        StyleFields::deserialize(de::value::MapAccessDeserializer::new(map)).map(Style::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{style, Color::*, Style};
+
+    #[test]
+    fn serialize_works() {
+        assert_eq!(serde_json::to_string(&Red).unwrap(), r#""Red""#);
+        assert_eq!(
+            serde_json::to_string(&style!(Red + i + b)).unwrap(),
+            r#""Red+bold+italic""#
+        );
+        assert_eq!(serde_json::to_string(&style!()).unwrap(), r#""""#);
+        assert_eq!(serde_json::to_string(&Fixed(208)).unwrap(), r#""208""#);
+    }
+
+    #[test]
+    fn deserialize_works() {
+        let parse = |json| serde_json::from_str::<Style>(json).unwrap();
+
+        assert_eq!(parse(r#""Red+bold""#), style!(Red + bold));
+        assert_eq!(parse(r#""+i+u""#), style!(+i+u));
+        assert_eq!(parse(r#""""#), style!());
+
+        assert_eq!(
+            parse(r#"{ "color": "blue", "italic": true }"#),
+            style!(Blue + italic)
+        );
+        assert_eq!(parse(r#"{ "dim": true }"#), style!(+d));
+        assert_eq!(
+            parse(r##"{ "color": "208", "background": "#000000" }"##),
+            style!().color(Fixed(208)).background(Rgb(0, 0, 0))
+        );
+        assert_eq!(parse("{}"), style!());
+
+        assert!(serde_json::from_str::<Style>(r#""Red+heavy""#).is_err());
+        assert!(serde_json::from_str::<Style>(r#"{ "heavy": true }"#).is_err());
+        assert!(serde_json::from_str::<Style>("3").is_err());
+    }
+
+    #[test]
+    fn round_trip_works() {
+        let style = style!(Magenta + u + d);
+        let json = serde_json::to_string(&style).unwrap();
+        assert_eq!(serde_json::from_str::<Style>(&json).unwrap(), style);
+    }
+
+    #[test]
+    fn round_trip_works_w_binary() {
+        for style in [
+            style!(),
+            style!(Magenta + u + d),
+            style!(+b).background(Rgb(1, 2, 3)),
+        ] {
+            let bytes = bincode::serialize(&style).unwrap();
+            assert_eq!(bincode::deserialize::<Style>(&bytes).unwrap(), style);
+        }
+
+        let bytes = bincode::serialize(&Fixed(208)).unwrap();
+        assert_eq!(
+            bincode::deserialize::<crate::Color>(&bytes).unwrap(),
+            Fixed(208)
+        );
+    }
+}
@@ -2,6 +2,8 @@
 /// 
 /// Similar to `Style` struct builder pattern.
 ///
+/// Can be used in `const` and `static` declarations.
+///
 /// # Examples
 ///
 /// ```
@@ -17,22 +19,121 @@
 /// // Default color, italic and bold
 /// let style = style!(+i+b);
 /// println!("{}", style.format("Hello"));
+///
+/// // Constant
+/// const ERROR: stilo::Style = style!(Red + bold);
+/// println!("{}", ERROR.format("Hello"));
+///
+/// // Conditional, with no style if false
+/// let ok = true;
+/// let style = style!(Green + b if ok);
+/// println!("{}", style.format("Hello"));
+///
+/// // Conditional, with a style if false
+/// let style = style!(Green if ok else Red + b);
+/// println!("{}", style.format("Hello"));
+///
+/// // Chained conditions, and existing styles
+/// let warn = false;
+/// let style = style!(Green if ok else Yellow if warn else {ERROR});
+/// println!("{}", style.format("Hello"));
+/// ```
+///
+/// Invalid styles are a compile error
+///
+/// ```compile_fail
+/// # use stilo::style;
+/// let style = style!(Red Blue);
 /// ```
 #[macro_export]
 macro_rules! style {
-    // Blank style
-    () => {
+    // Internal: blank style
+    ( @plain ) => {
         $crate::Style::new()
     };
 
-    // Color and decoration
-    ( $( $color: ident )? $( + $decor: ident )* ) => {
+    // Internal: color and decoration
+    ( @plain $( $color: ident )? $( + $decor: ident )* ) => {
         $crate::Style::new()
             $( .color($crate::Color::$color) )?
             $(
                 .$decor()
             )*
     };
+
+    // Internal: existing style
+    ( @plain $style: block ) => {
+        $style
+    };
+
+    // Internal: invalid style, with no condition
+    ( @plain $( $tt: tt )+ ) => {
+        compile_error!(concat!(
+            "invalid style `",
+            stringify!($( $tt )+),
+            "`, expected a color and decorations such as `Red + bold`, ",
+            "an existing style such as `{style}`, or a condition such as `Red if ok else Blue`",
+        ))
+    };
+
+    // Internal: end of style, with no condition
+    ( @style [ $( $style: tt )* ] ) => {
+        $crate::style!( @plain $( $style )* )
+    };
+
+    // Internal: end of style, start of condition
+    ( @style [ $( $style: tt )* ] if $( $rest: tt )* ) => {
+        $crate::style!( @condition [ $( $style )* ] [] $( $rest )* )
+    };
+
+    // Internal: add token to style
+    ( @style [ $( $style: tt )* ] $next: tt $( $rest: tt )* ) => {
+        $crate::style!( @style [ $( $style )* $next ] $( $rest )* )
+    };
+
+    // Internal: end of condition, with no `else`
+    ( @condition [ $( $style: tt )* ] [ $( $condition: tt )+ ] ) => {
+        if $( $condition )+ {
+            $crate::style!( @plain $( $style )* )
+        } else {
+            $crate::Style::new()
+        }
+    };
+
+    // Internal: end of condition, with `else`
+    ( @condition [ $( $style: tt )* ] [ $( $condition: tt )+ ] else $( $rest: tt )+ ) => {
+        if $( $condition )+ {
+            $crate::style!( @plain $( $style )* )
+        } else {
+            $crate::style!( @style [] $( $rest )+ )
+        }
+    };
+
+    // Internal: add token to condition
+    ( @condition [ $( $style: tt )* ] [ $( $condition: tt )* ] $next: tt $( $rest: tt )* ) => {
+        $crate::style!( @condition [ $( $style )* ] [ $( $condition )* $next ] $( $rest )* )
+    };
+
+    // Blank style
+    () => {
+        $crate::style!( @plain )
+    };
+
+    // Color and decoration
+    ( $( $color: ident )? $( + $decor: ident )* ) => {
+        $crate::style!( @plain $( $color )? $( + $decor )* )
+    };
+
+    // Existing style
+    ( $style: block ) => {
+        $crate::style!( @plain $style )
+    };
+
+    // Conditional, with optional `else`
+    // Anything else is an error, from `@plain`, at the end of the style
+    ( $( $tt: tt )+ ) => {
+        $crate::style!( @style [] $( $tt )+ )
+    };
 }
 
 #[cfg(test)]
@@ -87,4 +188,53 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn style_works_w_conditions() {
+        let error = style!(Red + bold);
+
+        assert_eq!(style!(Red if true), style!(Red));
+        assert_eq!(style!(Red + i if 1 > 2), style!());
+        assert_eq!(style!(+u if true), style!(+u));
+
+        assert_eq!(style!(Green if true else Red), style!(Green));
+        assert_eq!(style!(Green + b if false else Red + i), style!(Red + i));
+        assert_eq!(style!(if false else Red), style!(Red));
+        assert_eq!(style!({error} if true else Green), error);
+        assert_eq!(style!(Green if error.italic else {error}), error);
+
+        let style = |level: u8| style!(Green if level == 0 else Yellow + b if level == 1 else Red);
+        assert_eq!(style(0), style!(Green));
+        assert_eq!(style(1), style!(Yellow + b));
+        assert_eq!(style(2), style!(Red));
+
+        const ERROR: Style = style!(Red if 1 > 2 else Blue + u);
+        assert_eq!(ERROR, style!(Blue + u));
+    }
+
+    #[test]
+    fn style_works_in_const() {
+        const ERROR: Style = style!(Red + bold);
+        static WARNING: Style = style!(Yellow + i+u);
+        const EMPTY: Style = style!();
+
+        assert_eq!(
+            ERROR,
+            Style {
+                color: Some(Red),
+                bold: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            WARNING,
+            Style {
+                color: Some(Yellow),
+                italic: true,
+                underline: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(EMPTY, Style::new());
+    }
 }
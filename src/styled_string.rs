@@ -0,0 +1,250 @@
+use std::fmt;
+
+use crate::{
+    terminal::{escape_len, reapply_after_resets, update_style_state, visible_width},
+    Style,
+};
+
+/// Span of text with a `Style`, in a `StyledString`
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Span {
+    /// Text, which may contain ANSI codes, such as from `stylize!`
+    pub text: String,
+    /// Style of the text
+    pub style: Style,
+}
+
+/// Text made of many spans, each with its own `Style`
+///
+/// Displays as text with ANSI codes
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{style, StyledString};
+/// let mut text = StyledString::new();
+/// text.push("error", style!(Red + bold));
+/// text.push_plain(": something went wrong");
+///
+/// println!("{}", text);
+/// assert_eq!(text.plain(), "error: something went wrong");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StyledString {
+    spans: Vec<Span>,
+}
+
+impl StyledString {
+    /// Create an empty `StyledString`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add text with a `Style`
+    ///
+    /// Joins with the last span if it has the same style
+    pub fn push(&mut self, text: impl Into<String>, style: Style) {
+        let text = text.into();
+        if text.is_empty() {
+            return;
+        }
+
+        match self.spans.last_mut() {
+            Some(last) if last.style == style => last.text += &text,
+            _ => self.spans.push(Span { text, style }),
+        }
+    }
+
+    /// Add text with no style
+    pub fn push_plain(&mut self, text: impl Into<String>) {
+        self.push(text, Style::new());
+    }
+
+    /// Add all spans of another `StyledString`
+    pub fn append(&mut self, other: StyledString) {
+        for span in other.spans {
+            self.push(span.text, span.style);
+        }
+    }
+
+    /// Add all spans of another `StyledString`, with a `Style` under the style of each span
+    ///
+    /// The style is applied again after resets in the text, so ANSI codes in the text do not
+    /// remove it
+    pub(crate) fn append_under(&mut self, other: &StyledString, style: Style) {
+        for span in &other.spans {
+            let style = style.merge(span.style);
+            let prefix = style.prefix().to_string();
+            self.push(reapply_after_resets(&span.text, &prefix), style);
+        }
+    }
+
+    /// Get the spans of text
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// Check if there is no text
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Get the text, without the styles of spans
+    ///
+    /// ANSI codes in the text are kept
+    pub fn plain(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    /// Get the number of terminal columns, without styles or any ANSI codes in the text
+    ///
+    /// Wide characters, such as CJK and emoji, take 2 columns, and zero-width characters take none
+    pub fn width(&self) -> usize {
+        self.spans
+            .iter()
+            .map(|span| visible_width(&span.text))
+            .sum()
+    }
+
+    /// Split into lines, at newlines
+    ///
+    /// ANSI codes in the text are reset at the end of each line, and applied again on the next
+    /// line, so text around each line is not styled
+    pub(crate) fn lines(&self) -> Vec<StyledString> {
+        let mut lines = vec![StyledString::new()];
+        // Escape codes which apply the current style of the text
+        let mut state = String::new();
+
+        for span in &self.spans {
+            for (i, part) in span.text.split('\n').enumerate() {
+                if i > 0 {
+                    if !state.is_empty() {
+                        lines.last_mut().unwrap().push_plain("\x1b[0m");
+                    }
+                    let mut line = StyledString::new();
+                    line.push_plain(state.as_str());
+                    lines.push(line);
+                }
+
+                let mut rest = part;
+                while let Some(start) = rest.find('\x1b') {
+                    let len = escape_len(&rest[start..]);
+                    update_style_state(&mut state, &rest[start..start + len]);
+                    rest = &rest[start + len..];
+                }
+                lines.last_mut().unwrap().push(part, span.style);
+            }
+        }
+
+        lines
+    }
+
+    /// Format text with the `Style` of each span
+    pub fn format(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for StyledString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for span in &self.spans {
+            write!(f, "{}", span.style.format(&span.text))?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for StyledString {
+    fn from(text: &str) -> Self {
+        let mut string = Self::new();
+        string.push_plain(text);
+        string
+    }
+}
+
+impl From<String> for StyledString {
+    fn from(text: String) -> Self {
+        let mut string = Self::new();
+        string.push_plain(text);
+        string
+    }
+}
+
+impl<T: Into<String>> FromIterator<(T, Style)> for StyledString {
+    fn from_iter<I: IntoIterator<Item = (T, Style)>>(iter: I) -> Self {
+        let mut string = Self::new();
+        for (text, style) in iter {
+            string.push(text, style);
+        }
+        string
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{style, stylize};
+
+    #[test]
+    fn styled_string_works() {
+        let mut text = StyledString::new();
+        assert!(text.is_empty());
+        assert_eq!(text.to_string(), "");
+
+        text.push("Hello", style!(Red));
+        text.push_plain(", ");
+        text.push("World", style!(Blue + b));
+        text.push("!", style!(Blue + b));
+        text.push("", style!(Green));
+
+        assert_eq!(text.spans().len(), 3);
+        assert_eq!(text.plain(), "Hello, World!");
+        assert_eq!(text.width(), 13);
+        assert_eq!(
+            text.to_string(),
+            "\x1b[31mHello\x1b[0m, \x1b[34;1mWorld!\x1b[0m"
+        );
+        assert_eq!(text.format(), text.to_string());
+    }
+
+    #[test]
+    fn styled_string_converts() {
+        assert_eq!(StyledString::from("abc").plain(), "abc");
+        assert_eq!(StyledString::from(String::from("é")).width(), 1);
+        assert_eq!(StyledString::from(stylize!("ab": Red)).width(), 2);
+
+        let text: StyledString = [("a", style!(Red)), ("b", style!(Red)), ("c", style!())]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            text.spans(),
+            [
+                Span {
+                    text: "ab".into(),
+                    style: style!(Red)
+                },
+                Span {
+                    text: "c".into(),
+                    style: style!()
+                },
+            ]
+        );
+
+        let mut other = StyledString::from("x");
+        other.append(text);
+        assert_eq!(other.plain(), "xabc");
+        assert_eq!(other.spans().len(), 3);
+    }
+
+    #[test]
+    fn lines_works() {
+        let text = StyledString::from(stylize!("a\nb": Red) + "\nc");
+        let lines: Vec<_> = text.lines().iter().map(|line| line.to_string()).collect();
+        assert_eq!(lines, ["\x1b[31ma\x1b[0m", "\x1b[31mb\x1b[0m", "c"]);
+
+        let mut text = StyledString::new();
+        text.push("a\nb", style!(+b));
+        let lines: Vec<_> = text.lines().iter().map(|line| line.to_string()).collect();
+        assert_eq!(lines, ["\x1b[1ma\x1b[0m", "\x1b[1mb\x1b[0m"]);
+    }
+}
@@ -0,0 +1,251 @@
+use std::io::{self, BufWriter, Write};
+
+use crate::{Stream, Style, StyledString};
+
+/// Message for the writer after `into_inner`, which cannot be used again
+const TAKEN: &str = "writer should only be taken by `into_inner`";
+
+/// Writer that tracks the current `Style`, and only writes escape codes when the style changes
+///
+/// If color is not enabled, no escape codes are written.
+///
+/// Output is buffered, except for `StyledWriter::stderr`, and flushed when the writer is dropped,
+/// or with `flush`. The style is reset when the writer is dropped, or with `into_inner`.
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{style, StyledWriter};
+/// let mut writer = StyledWriter::stderr();
+///
+/// writer.write_styled(&style!(Red + bold), "error").unwrap();
+/// writer.write_styled(&style!(), ": ").unwrap();
+/// writer.write_styled(&style!(+i), "something went wrong\n").unwrap();
+/// writer.reset().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct StyledWriter<W: Write> {
+    /// Only `None` after `into_inner`
+    writer: Option<BufWriter<W>>,
+    color: bool,
+    style: Style,
+}
+
+impl<W: Write> StyledWriter<W> {
+    /// Create a `StyledWriter`, with color enabled or disabled
+    pub fn new(writer: W, color: bool) -> Self {
+        Self {
+            writer: Some(BufWriter::new(writer)),
+            color,
+            style: Style::new(),
+        }
+    }
+
+    /// Create a `StyledWriter` with a buffer of a number of bytes
+    ///
+    /// With a capacity of `0`, text is written directly, with no buffer
+    pub fn with_capacity(capacity: usize, writer: W, color: bool) -> Self {
+        Self {
+            writer: Some(BufWriter::with_capacity(capacity, writer)),
+            color,
+            style: Style::new(),
+        }
+    }
+
+    /// Check if color is enabled
+    pub fn color_enabled(&self) -> bool {
+        self.color
+    }
+
+    /// Get the current `Style`
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Change the current `Style`
+    ///
+    /// Writes nothing if the style is the same
+    pub fn set_style(&mut self, style: Style) -> io::Result<()> {
+        if style == self.style {
+            return Ok(());
+        }
+
+        if self.color {
+            // Decorations cannot be removed individually, so reset first
+            let suffix = self.style.suffix();
+            write!(self.buffer(), "{suffix}{}", style.prefix())?;
+        }
+        self.style = style;
+        Ok(())
+    }
+
+    /// Reset to no style
+    pub fn reset(&mut self) -> io::Result<()> {
+        self.set_style(Style::new())
+    }
+
+    /// Write text with a `Style`
+    ///
+    /// The style is kept after writing, so following text with the same style needs no escape codes
+    pub fn write_styled(&mut self, style: &Style, text: &str) -> io::Result<()> {
+        self.set_style(*style)?;
+        self.buffer().write_all(text.as_bytes())
+    }
+
+    /// Write each span of a `StyledString`
+    pub fn write_string(&mut self, text: &StyledString) -> io::Result<()> {
+        for span in text.spans() {
+            self.write_styled(&span.style, &span.text)?;
+        }
+        Ok(())
+    }
+
+    /// Get a reference to the underlying writer
+    ///
+    /// Buffered text is not written yet. Use `flush` first.
+    pub fn get_ref(&self) -> &W {
+        self.writer.as_ref().expect(TAKEN).get_ref()
+    }
+
+    /// Get a mutable reference to the underlying writer
+    ///
+    /// Buffered text is not written yet, so use `flush` before writing directly.
+    /// Writing escape codes directly may change the style, without the `StyledWriter` knowing
+    pub fn get_mut(&mut self) -> &mut W {
+        self.buffer().get_mut()
+    }
+
+    /// Reset the style, flush buffered text, and get the underlying writer
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.reset()?;
+        let writer = self.writer.take().expect(TAKEN);
+        writer.into_inner().map_err(|error| error.into_error())
+    }
+
+    /// Get the buffered writer
+    fn buffer(&mut self) -> &mut BufWriter<W> {
+        self.writer.as_mut().expect(TAKEN)
+    }
+}
+
+impl StyledWriter<io::Stdout> {
+    /// Create a `StyledWriter` for stdout, with color if enabled for stdout
+    ///
+    /// See `Stream::color_enabled`
+    pub fn stdout() -> Self {
+        Self::new(io::stdout(), Stream::Stdout.color_enabled())
+    }
+}
+
+impl StyledWriter<io::Stderr> {
+    /// Create a `StyledWriter` for stderr, with color if enabled for stderr
+    ///
+    /// Not buffered, so text is written straight away. See `Stream::color_enabled`
+    pub fn stderr() -> Self {
+        Self::with_capacity(0, io::stderr(), Stream::Stderr.color_enabled())
+    }
+}
+
+/// Writes text with the current `Style`
+impl<W: Write> Write for StyledWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.buffer().flush()
+    }
+}
+
+/// Resets the style, then flushes buffered text, ignoring errors
+impl<W: Write> Drop for StyledWriter<W> {
+    fn drop(&mut self) {
+        if self.writer.is_some() {
+            let _ = self.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{style, stylize_many};
+
+    #[test]
+    fn styled_writer_works() {
+        let mut writer = StyledWriter::new(Vec::new(), true);
+        assert!(writer.color_enabled());
+
+        writer.write_styled(&style!(Red), "a").unwrap();
+        writer.write_styled(&style!(Red), "b").unwrap();
+        writer.write_styled(&style!(Red + b), "c").unwrap();
+        writer.write_styled(&style!(), "d").unwrap();
+        writer.write_styled(&style!(+i), "e").unwrap();
+        assert_eq!(writer.style(), style!(+i));
+        writer.reset().unwrap();
+        writer.reset().unwrap();
+
+        assert_eq!(
+            String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+            "\x1b[31mab\x1b[0m\x1b[31;1mc\x1b[0md\x1b[3me\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn styled_writer_works_wo_color() {
+        let mut writer = StyledWriter::new(Vec::new(), false);
+
+        writer.write_styled(&style!(Red), "a").unwrap();
+        writer.set_style(style!(Blue + u)).unwrap();
+        assert_eq!(writer.style(), style!(Blue + u));
+        write!(writer, "b{}", 1).unwrap();
+        writer.reset().unwrap();
+
+        // Buffered until flushed
+        assert_eq!(writer.get_ref(), b"");
+        writer.flush().unwrap();
+        assert_eq!(writer.get_ref(), b"ab1");
+    }
+
+    #[test]
+    fn styled_writer_resets_on_drop() {
+        let mut bytes = Vec::new();
+        {
+            let mut writer = StyledWriter::new(&mut bytes, true);
+            writer.write_styled(&style!(Red), "a").unwrap();
+        }
+        assert_eq!(bytes, b"\x1b[31ma\x1b[0m");
+
+        let mut writer = StyledWriter::new(Vec::new(), true);
+        writer.write_styled(&style!(+b), "a").unwrap();
+        assert_eq!(writer.into_inner().unwrap(), b"\x1b[1ma\x1b[0m");
+
+        let mut writer = StyledWriter::new(Vec::new(), false);
+        writer.write_styled(&style!(+b), "a").unwrap();
+        assert_eq!(writer.into_inner().unwrap(), b"a");
+    }
+
+    #[test]
+    fn styled_writer_works_wo_buffer() {
+        let mut writer = StyledWriter::with_capacity(0, Vec::new(), true);
+        writer.write_styled(&style!(Red), "a").unwrap();
+        assert_eq!(writer.get_ref(), b"\x1b[31ma");
+    }
+
+    #[test]
+    fn styled_writer_writes_string() {
+        let mut text = StyledString::new();
+        text.push("error", style!(Red + b));
+        text.push_plain(": ");
+        text.push("oops", style!(+i));
+
+        let mut writer = StyledWriter::new(Vec::new(), true);
+        writer.write_string(&text).unwrap();
+        writer.reset().unwrap();
+
+        assert_eq!(
+            String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+            stylize_many!("error": Red + b; ": "; "oops": +i)
+        );
+    }
+}
@@ -12,6 +12,10 @@ macro_rules! fallback_metavar {(
 ///
 /// Similar to the `stylize` function.
 ///
+/// Text with no format arguments, and a style of a color and decorations, is styled at compile time,
+/// and copied into the returned `String`. Use `stylize_static!` for a `&'static str`, with no
+/// allocation.
+///
 /// # Examples
 ///
 /// ```
@@ -28,9 +32,41 @@ macro_rules! fallback_metavar {(
 /// // Format string
 /// let world = "World!";
 /// println!("{}", stylize!("Hello {}": Green + i+b, world));
+///
+/// // Text from an expression, not a format string
+/// let name = String::from("World!");
+/// println!("{}", stylize!(name => Blue + u));
+/// println!("{}", stylize!(name.len() => Yellow if name.is_empty()));
+///
+/// // Style if condition is false
+/// let ok = false;
+/// println!("{}", stylize!("{}": Green if ok else Red + b, name));
+/// println!("{}", stylize!(name => Green if ok else Yellow if name.is_empty() else Red));
+/// ```
+///
+/// Invalid styles are a compile error
+///
+/// ```compile_fail
+/// # use stilo::stylize;
+/// println!("{}", stylize!("Hello": Red Blue));
 /// ```
 #[macro_export]
 macro_rules! stylize {
+    // Internal: end of style, with format arguments
+    (
+        @else $text: literal [ $( $style: tt )+ ]
+        $(, $arg: expr )* $(,)?
+    ) => {
+        $crate::style!( $( $style )+ ).format(
+            &format!($text, $( $arg, )*)
+        )
+    };
+
+    // Internal: add token to style
+    ( @else $text: literal [ $( $style: tt )* ] $next: tt $( $rest: tt )* ) => {
+        $crate::stylize!( @else $text [ $( $style )* $next ] $( $rest )* )
+    };
+
     // No style, just text
     (
         $text: literal
@@ -64,6 +100,29 @@ macro_rules! stylize {
         }
     };
 
+    // Color and decoration, with no format arguments
+    // Styled at compile time, unless the text has implicit arguments, such as `{name}`
+    // The branch is a constant, so only one is kept
+    (
+        $text: literal :
+        $( $color: ident )?
+        $( + $decor: ident )*
+        $( if $condition: expr )? $(,)?
+    ) => {{
+        const __HAS_ARGS: bool = $crate::has_format_args($text);
+        if __HAS_ARGS {
+            $crate::stylize!(
+                $text : { $crate::style!( $( $color )? $( + $decor )* ) }
+                $( if $condition )?
+            )
+        } else {
+            String::from($crate::stylize_static!(
+                @unchecked $text : { $crate::style!( $( $color )? $( + $decor )* ) }
+                $( if $condition )?
+            ))
+        }
+    }};
+
     // Color and decoration
     (
         $text: literal :
@@ -85,6 +144,57 @@ macro_rules! stylize {
             format!($text, $( $arg, )*)
         }
     };
+
+    // Style with `else`
+    (
+        $text: literal :
+        $( $rest: tt )+
+    ) => {
+        $crate::stylize!( @else $text [] $( $rest )+ )
+    };
+
+    // Expression, conditional with no style
+    (
+        $text: expr =>
+        $( if $condition: expr )? $(,)?
+    ) => {
+        compile_error!("Cannot use conditional style, if no styles are included");
+    };
+
+    // Expression, with existing style
+    (
+        $text: expr =>
+        $style: block
+        $( if $condition: expr )? $(,)?
+    ) => {{
+        let text = $text.to_string();
+        if $crate::fallback_metavar!( $({ $condition })? {true} ) {
+            $style.format(&text)
+        } else {
+            text
+        }
+    }};
+
+    // Expression, color and decoration
+    (
+        $text: expr =>
+        $( $color: ident )?
+        $( + $decor: ident )*
+        $( if $condition: expr )? $(,)?
+    ) => {
+        $crate::stylize!(
+            $text => { $crate::style!( $( $color )? $( + $decor )* ) }
+            $( if $condition )?
+        )
+    };
+
+    // Expression, style with `else`
+    (
+        $text: expr =>
+        $( $rest: tt )+
+    ) => {
+        $crate::style!( $( $rest )+ ).format(&$text.to_string())
+    };
 }
 
 #[cfg(test)]
@@ -103,6 +213,9 @@ mod tests {
 
         assert_eq!(stylize!("Hello": Red), "\x1b[31mHello\x1b[0m");
         assert_eq!(stylize!("Hello": Red,), "\x1b[31mHello\x1b[0m");
+        assert_eq!(stylize!("{{Hello}}": Red), "\x1b[31m{Hello}\x1b[0m");
+        assert_eq!(stylize!("{world}": Red), "\x1b[31mWorld!\x1b[0m");
+        assert_eq!(stylize!("{world:?}": +b if false), "\"World!\"");
         assert_eq!(
             stylize!("Hello {}": Red, world,),
             "\x1b[31mHello World!\x1b[0m"
@@ -184,4 +297,59 @@ mod tests {
             "Hello"
         );
     }
+
+    #[test]
+    fn stylize_works_w_expressions() {
+        let world = String::from("World!");
+        let words = ["Hello", "{}"];
+
+        assert_eq!(stylize!(world => Red), "\x1b[31mWorld!\x1b[0m");
+        assert_eq!(stylize!(&world => Red + bold), "\x1b[31;1mWorld!\x1b[0m");
+        assert_eq!(stylize!(world => +i+u), "\x1b[3;4mWorld!\x1b[0m");
+        assert_eq!(stylize!(world.len() => Green), "\x1b[32m6\x1b[0m");
+        assert_eq!(stylize!(words[1] => Blue), "\x1b[34m{}\x1b[0m");
+        assert_eq!(stylize!("{}" => Blue), "\x1b[34m{}\x1b[0m");
+        assert_eq!(stylize!(world => Red,), "\x1b[31mWorld!\x1b[0m");
+
+        assert_eq!(stylize!(world => Red if true), "\x1b[31mWorld!\x1b[0m");
+        assert_eq!(stylize!(world => Red + b if world.is_empty()), "World!");
+
+        let style = style!(Red + italic);
+
+        assert_eq!(stylize!(world => {style}), "\x1b[31;3mWorld!\x1b[0m");
+        assert_eq!(stylize!(words[0] => {style} if true), "\x1b[31;3mHello\x1b[0m");
+        assert_eq!(stylize!(world => {style} if false), "World!");
+        assert_eq!(stylize!(world => {style!(Blue)}), "\x1b[34mWorld!\x1b[0m");
+    }
+
+    #[test]
+    fn stylize_works_w_else() {
+        let world = "World!";
+        let style = style!(Red + italic);
+
+        assert_eq!(stylize!("Hello": Green if true else Red), "\x1b[32mHello\x1b[0m");
+        assert_eq!(stylize!("Hello": Green if false else Red), "\x1b[31mHello\x1b[0m");
+        assert_eq!(
+            stylize!("Hello {}": Green if false else Red + b, world),
+            "\x1b[31;1mHello World!\x1b[0m"
+        );
+        assert_eq!(
+            stylize!("Hello {world} {}": +u if world.is_empty() else {style}, 123,),
+            "\x1b[31;3mHello World! 123\x1b[0m"
+        );
+        assert_eq!(
+            stylize!("Hello": Green if false else Yellow if true else Red),
+            "\x1b[33mHello\x1b[0m"
+        );
+        assert_eq!(stylize!("Hello": Green if false else {style!()}), "Hello");
+
+        assert_eq!(
+            stylize!(world => Green if false else {style}),
+            "\x1b[31;3mWorld!\x1b[0m"
+        );
+        assert_eq!(
+            stylize!(world.len() => Green if false else Yellow if false else Red + b),
+            "\x1b[31;1m6\x1b[0m"
+        );
+    }
 }
@@ -8,6 +8,7 @@
 /// New lines will **not** be added between arguments.
 ///
 /// Each argument is used the same as the `stylize!` macro.
+/// Arguments can use different kinds of styles in the same call.
 ///
 /// # Examples
 ///
@@ -25,10 +26,35 @@
 ///     "Hello\n": +i+b;
 ///     // Format string
 ///     "Hello {}": Green + i+b, world;
+///     // Expression
+///     world => Blue;
+/// ));
+///
+/// // Existing styles, mixed with other styles
+/// let style = stilo::style!(Yellow + u);
+/// println!("{}", stylize_many!(
+///     "Hello": {style};
+///     " ";
+///     world => Red + b;
+///     "!": {style} if world.is_empty();
+/// ));
+///
+/// // Style if condition is false
+/// let ok = true;
+/// println!("{}", stylize_many!(
+///     "Status: ";
+///     "{}": Green if ok else Red + b, if ok { "passed" } else { "failed" };
 /// ));
 /// ```
 #[macro_export]
 macro_rules! stylize_many {
+    // Internal: stylize each argument, after splitting with `split_segments!`
+    ( @segments $output: ident $( [ $( $segment: tt )+ ] )* ) => {
+        $(
+            $output += &$crate::stylize!( $( $segment )+ );
+        )*
+    };
+
     // Creating styles
     (
         $(
@@ -84,6 +110,14 @@ macro_rules! stylize_many {
         )*
         text
     }};
+
+    // Any arguments, including expressions, and mixed styles
+    // Each argument is passed to `stylize!`
+    ( $( $arg: tt )* ) => {{
+        let mut text = String::new();
+        $crate::split_segments!( [$crate::stylize_many] [@segments text] $( $arg )* );
+        text
+    }};
 }
 
 #[cfg(test)]
@@ -145,6 +179,135 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn stylize_many_works_w_expressions() {
+        let world = String::from("World!");
+        let style = style!(Yellow + italic);
+
+        let actual = stylize_many!(
+            world => Red;
+            "\n" => Red;
+            world.len() => +b;
+            "\n";
+            world => Red if false;
+            "\nHello {}": Blue, world;
+            "\n"; ;
+            world => {style};
+            "\n": Green;
+            world => {style} if false
+        );
+
+        let expected = concat!(
+            "\x1b[31mWorld!\x1b[0m",
+            "\x1b[31m\n\x1b[0m",
+            "\x1b[1m6\x1b[0m",
+            "\n",
+            "World!",
+            "\x1b[34m\nHello World!\x1b[0m",
+            "\n",
+            "\x1b[33;3mWorld!\x1b[0m",
+            "\x1b[32m\n\x1b[0m",
+            "World!",
+        );
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn stylize_many_works_w_mixed_styles() {
+        let world = "World!";
+        let style = style!(Yellow + italic);
+
+        let actual = stylize_many!(
+            "Hello": Red;
+            "\nHello": {style};
+            "\nHello {}": +b, world;
+            "\nHello {}": {style} if false, world;
+            "\nHello";
+            "\nHello": {style!(Blue)} if true;
+            "\n" => Green;
+            "Hello": Red + u if true,
+        );
+
+        let expected = concat!(
+            "\x1b[31mHello\x1b[0m",
+            "\x1b[33;3m\nHello\x1b[0m",
+            "\x1b[1m\nHello World!\x1b[0m",
+            "\nHello World!",
+            "\nHello",
+            "\x1b[34m\nHello\x1b[0m",
+            "\x1b[32m\n\x1b[0m",
+            "\x1b[31;4mHello\x1b[0m",
+        );
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn stylize_many_works_w_else() {
+        let world = "World!";
+        let style = style!(Yellow + italic);
+
+        let actual = stylize_many!(
+            "Hello": Red if false else Blue;
+            "\nHello {}": Green if true else {style}, world;
+            "\n";
+            world => Green if false else Red if false else {style};
+            "\nHello": Red if false;
+        );
+
+        let expected = concat!(
+            "\x1b[34mHello\x1b[0m",
+            "\x1b[32m\nHello World!\x1b[0m",
+            "\n",
+            "\x1b[33;3mWorld!\x1b[0m",
+            "\nHello",
+        );
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn stylize_many_works_w_many_mixed_styles() {
+        let style = style!(Blue);
+
+        // More arguments than the default recursion limit
+        let actual = stylize_many!(
+            "a": Red; "b": {style}; "a": Red; "b": {style}; "a": Red; "b": {style}; "a": Red; "b": {style};
+            "a": Red; "b": {style}; "a": Red; "b": {style}; "a": Red; "b": {style}; "a": Red; "b": {style};
+            "a": Red; "b": {style}; "a": Red; "b": {style}; "a": Red; "b": {style}; "a": Red; "b": {style};
+            "a": Red; "b": {style}; "a": Red; "b": {style}; "a": Red; "b": {style}; "a": Red; "b": {style};
+            "a": Red; "b": {style}; "a": Red; "b": {style}; "a": Red; "b": {style}; "a": Red; "b": {style};
+            "a": Red; "b": {style}; "a": Red; "b": {style}; "a": Red; "b": {style}; "a": Red; "b": {style};
+            "a": Red; "b": {style}; "a": Red; "b": {style}; "a": Red; "b": {style}; "a": Red; "b": {style};
+            "a": Red; "b": {style}; "a": Red; "b": {style}; "a": Red; "b": {style}; "a": Red; "b": {style};
+        );
+
+        assert_eq!(actual, "\x1b[31ma\x1b[0m\x1b[34mb\x1b[0m".repeat(32));
+    }
+
+    #[test]
+    fn stylize_many_works_w_many_expressions() {
+        let w = "ab";
+
+        // Each argument is longer than the recursion limit, when split one token at a time
+        let actual = stylize_many!(
+            w.len() => +b; w.len() => +b; w.len() => +b; w.len() => +b; w.len() => +b;
+            w.len() => +b; w.len() => +b; w.len() => +b; w.len() => +b; w.len() => +b;
+            w.len() => +b; w.len() => +b; w.len() => +b; w.len() => +b; w.len() => +b;
+            w.len() => +b; w.len() => +b; w.len() => +b; w.len() => +b; w.len() => +b;
+            w.len() => +b; w.len() => +b; w.len() => +b; w.len() => +b; w.len() => +b;
+            w.len() => +b; w.len() => +b; w.len() => +b; w.len() => +b; w.len() => +b;
+            w.len() => +b; w.len() => +b; w.len() => +b; w.len() => +b; w.len() => +b;
+            w.len() => Green if w.is_empty() else Red + b; "{}": Blue, w.len()
+        );
+
+        assert_eq!(
+            actual,
+            "\x1b[1m2\x1b[0m".repeat(35) + "\x1b[31;1m2\x1b[0m\x1b[34m2\x1b[0m"
+        );
+    }
+
     #[test]
     fn stylize_many_works_w_existing_styles() {
         let world = "World!";
@@ -0,0 +1,255 @@
+use crate::Style;
+
+/// Creates a `&'static str` of stylized text, at compile time.
+///
+/// Similar to the `stylize!` macro, but with no runtime cost.
+/// Can be used in `const` and `static` declarations.
+///
+/// Text is written like a format string with no arguments, so `{{` and `}}` are written as `{` and `}`.
+/// Format arguments are not allowed, and the style must be able to be evaluated in `const` context.
+///
+/// `stylize!` also styles text at compile time, if there are no format arguments and the style
+/// is a color and decorations, but copies it into a `String`.
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::stylize_static;
+/// // Red and bold
+/// const ERROR: &str = stylize_static!("error:": Red + bold);
+/// println!("{} Something went wrong", ERROR);
+///
+/// // Default color, italic
+/// println!("{}", stylize_static!("note:": +i));
+///
+/// // Conditional
+/// let color = false;
+/// println!("{}", stylize_static!("warning:": Yellow if color));
+///
+/// // Existing style
+/// const LABEL: stilo::Style = stilo::style!(Green + u);
+/// println!("{}", stylize_static!("done": {LABEL}));
+/// ```
+///
+/// Format arguments are not allowed
+///
+/// ```compile_fail
+/// # use stilo::stylize_static;
+/// let world = "World!";
+/// println!("{}", stylize_static!("Hello {world}": Red));
+/// ```
+#[macro_export]
+macro_rules! stylize_static {
+    // Internal: with existing style, without checking for format arguments
+    (
+        @unchecked $text: literal :
+        $style: block
+        $( if $condition: expr )?
+    ) => {{
+        const __STYLE: $crate::Style = $style;
+        const __LEN: usize = $crate::static_len(__STYLE, $text);
+        const __BYTES: [u8; __LEN] = $crate::static_bytes(__STYLE, $text);
+        const __STYLED: &str = $crate::static_str(&__BYTES);
+
+        const __TEXT_LEN: usize = $crate::static_len($crate::Style::new(), $text);
+        const __TEXT_BYTES: [u8; __TEXT_LEN] = $crate::static_bytes($crate::Style::new(), $text);
+        const __TEXT: &str = $crate::static_str(&__TEXT_BYTES);
+
+        if $crate::fallback_metavar!( $({ $condition })? {true} ) {
+            __STYLED
+        } else {
+            __TEXT
+        }
+    }};
+
+    // No style, just text
+    ( $text: literal ) => {
+        $crate::stylize_static!($text: {$crate::Style::new()})
+    };
+
+    // Conditional, with no style
+    ( $text: literal : $( if $condition: expr )? ) => {
+        compile_error!("Cannot use conditional style, if no styles are included");
+    };
+
+    // With existing style
+    (
+        $text: literal :
+        $style: block
+        $( if $condition: expr )?
+    ) => {{
+        const _: () = assert!(
+            !$crate::has_format_args($text),
+            "`stylize_static!` cannot use format arguments, use `stylize!` instead"
+        );
+        $crate::stylize_static!( @unchecked $text : $style $( if $condition )? )
+    }};
+
+    // Color and decoration
+    (
+        $text: literal :
+        $( $color: ident )?
+        $( + $decor: ident )*
+        $( if $condition: expr )?
+    ) => {
+        $crate::stylize_static!(
+            $text : { $crate::style!( $( $color )? $( + $decor )* ) }
+            $( if $condition )?
+        )
+    };
+}
+
+/// Don't use this
+#[doc(hidden)]
+pub const fn has_format_args(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'{' || bytes[i] == b'}' {
+            // Escaped brace, `{{` or `}}`
+            if i + 1 < bytes.len() && bytes[i + 1] == bytes[i] {
+                i += 2;
+                continue;
+            }
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Don't use this
+#[doc(hidden)]
+pub const fn static_len(style: Style, text: &str) -> usize {
+    let params = style.params();
+    let len = copy_unescaped(&mut [], 0, text.as_bytes());
+    if params.is_empty() {
+        len
+    } else {
+        "\x1b[".len() + params.as_bytes().len() + "m".len() + len + "\x1b[0m".len()
+    }
+}
+
+/// Don't use this
+#[doc(hidden)]
+pub const fn static_bytes<const N: usize>(style: Style, text: &str) -> [u8; N] {
+    let mut bytes = [0; N];
+    let params = style.params();
+
+    if params.is_empty() {
+        copy_unescaped(&mut bytes, 0, text.as_bytes());
+    } else {
+        let mut len = 0;
+        len = copy_bytes(&mut bytes, len, b"\x1b[");
+        len = copy_bytes(&mut bytes, len, params.as_bytes());
+        len = copy_bytes(&mut bytes, len, b"m");
+        len = copy_unescaped(&mut bytes, len, text.as_bytes());
+        copy_bytes(&mut bytes, len, b"\x1b[0m");
+    }
+
+    bytes
+}
+
+/// Don't use this
+#[doc(hidden)]
+pub const fn static_str(bytes: &'static [u8]) -> &'static str {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(_) => panic!("stylized text should be valid utf-8"),
+    }
+}
+
+/// Copy bytes into a buffer at an index, and return the new index
+const fn copy_bytes(buffer: &mut [u8], start: usize, bytes: &[u8]) -> usize {
+    let mut i = 0;
+    while i < bytes.len() {
+        buffer[start + i] = bytes[i];
+        i += 1;
+    }
+    start + bytes.len()
+}
+
+/// Copy text into a buffer at an index, writing `{{` and `}}` as `{` and `}`, and return the new
+/// index
+///
+/// Nothing is copied if the buffer is empty, to get the length
+const fn copy_unescaped(buffer: &mut [u8], start: usize, bytes: &[u8]) -> usize {
+    let mut len = start;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !buffer.is_empty() {
+            buffer[len] = bytes[i];
+        }
+        len += 1;
+        if (bytes[i] == b'{' || bytes[i] == b'}') && i + 1 < bytes.len() && bytes[i + 1] == bytes[i] {
+            i += 1;
+        }
+        i += 1;
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{style, stylize, Style};
+
+    #[test]
+    fn stylize_static_works() {
+        assert_eq!(stylize_static!("Hello"), "Hello");
+        assert_eq!(stylize_static!("Hello": Red), "\x1b[31mHello\x1b[0m");
+        assert_eq!(
+            stylize_static!("Hello": Red + bold),
+            "\x1b[31;1mHello\x1b[0m"
+        );
+        assert_eq!(
+            stylize_static!("Hello": Blue + italic + b),
+            "\x1b[34;1;3mHello\x1b[0m"
+        );
+        assert_eq!(
+            stylize_static!("Hello": +u+d+bold),
+            "\x1b[1;2;4mHello\x1b[0m"
+        );
+        assert_eq!(stylize_static!("": Red), "\x1b[31m\x1b[0m");
+        assert_eq!(stylize_static!("Héllo ✓": Green), "\x1b[32mHéllo ✓\x1b[0m");
+        assert_eq!(stylize_static!("{{a}} }}{{": Red), "\x1b[31m{a} }{\x1b[0m");
+        assert_eq!(stylize_static!("{{a}}": Red if false), "{a}");
+        assert_eq!(stylize_static!("{{a}}"), "{a}");
+
+        assert_eq!(
+            stylize_static!("Hello": Red if true),
+            "\x1b[31mHello\x1b[0m"
+        );
+        assert_eq!(stylize_static!("Hello": Red + i if false), "Hello");
+
+        const STYLE: Style = style!(Red + italic).background(crate::Color::Rgb(1, 20, 255));
+        assert_eq!(
+            stylize_static!("Hello": {STYLE}),
+            "\x1b[31;48;2;1;20;255;3mHello\x1b[0m"
+        );
+        assert_eq!(stylize_static!("Hello": {STYLE} if false), "Hello");
+        assert_eq!(stylize_static!("Hello": {style!()}), "Hello");
+    }
+
+    #[test]
+    fn stylize_static_matches_stylize() {
+        assert_eq!(stylize_static!("Hello": Red), stylize!("Hello": Red));
+        assert_eq!(
+            stylize_static!("Hello": Yellow + r+s+blink),
+            stylize!("Hello": Yellow + r+s+blink)
+        );
+        assert_eq!(
+            stylize_static!("Hello": {style!().color(crate::Color::Fixed(208))}),
+            stylize!("Hello": {style!().color(crate::Color::Fixed(208))})
+        );
+        assert_eq!(stylize_static!("{{a}}": +i), stylize!("{{a}}": +i));
+    }
+
+    #[test]
+    fn stylize_static_works_in_const() {
+        const ERROR: &str = stylize_static!("error:": Red + bold);
+        static WARNING: &str = stylize_static!("warning:": Yellow + b);
+
+        assert_eq!(ERROR, "\x1b[31;1merror:\x1b[0m");
+        assert_eq!(WARNING, "\x1b[33;1mwarning:\x1b[0m");
+    }
+}
@@ -0,0 +1,463 @@
+use std::fmt;
+
+use unicode_width::UnicodeWidthChar;
+
+use crate::{border::BorderChars, terminal::escape_len, Border, Style, StyledString};
+
+/// Alignment of text in a column
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Alignment {
+    /// Text at the start, with spaces after
+    #[default]
+    Left,
+    /// Text in the middle, with any extra space after
+    Center,
+    /// Text at the end, with spaces before
+    Right,
+}
+
+/// Table of styled cells, with columns aligned by visible width
+///
+/// Cells can be `StyledString`s, or text with ANSI codes, such as from `stylize!`.
+/// Widths are measured in terminal columns, so wide characters, such as CJK and emoji, take 2.
+/// Cells should not contain newlines.
+///
+/// Create with `Table::new()`
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{style, stylize, Alignment, Border, Table};
+/// let table = Table::new()
+///     .header(["Name", "Size"])
+///     .row([stylize!("src": Blue + b), "4096".into()])
+///     .row(["Cargo.toml".into(), stylize!("512": Green)])
+///     .align(1, Alignment::Right)
+///     .border(Border::Rounded)
+///     .border_style(style!(+dim))
+///     .row_styles([style!(), style!(+i)]);
+///
+/// println!("{}", table);
+/// assert_eq!(
+///     stilo::strip_ansi(&table.to_string()),
+///     "\
+/// ╭────────────┬──────╮
+/// │ Name       │ Size │
+/// ├────────────┼──────┤
+/// │ src        │ 4096 │
+/// │ Cargo.toml │  512 │
+/// ╰────────────┴──────╯"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    header: Option<Vec<StyledString>>,
+    rows: Vec<Vec<StyledString>>,
+    alignments: Vec<Alignment>,
+    border: Border,
+    border_style: Style,
+    header_style: Style,
+    row_styles: Vec<Style>,
+    max_width: Option<usize>,
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Self {
+            header: None,
+            rows: Vec::new(),
+            alignments: Vec::new(),
+            border: Border::Single,
+            border_style: Style::new(),
+            header_style: Style::new().bold(),
+            row_styles: Vec::new(),
+            max_width: None,
+        }
+    }
+}
+
+impl Table {
+    /// Create an empty `Table`, with a single border and bold header
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the header row
+    pub fn header<T: Into<StyledString>>(mut self, cells: impl IntoIterator<Item = T>) -> Self {
+        self.header = Some(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add a row
+    pub fn row<T: Into<StyledString>>(mut self, cells: impl IntoIterator<Item = T>) -> Self {
+        self.push_row(cells);
+        self
+    }
+
+    /// Add a row, without taking ownership
+    pub fn push_row<T: Into<StyledString>>(&mut self, cells: impl IntoIterator<Item = T>) {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+    }
+
+    /// Set the `Alignment` of a column, starting at `0`
+    pub fn align(mut self, column: usize, alignment: Alignment) -> Self {
+        if self.alignments.len() <= column {
+            self.alignments.resize(column + 1, Alignment::Left);
+        }
+        self.alignments[column] = alignment;
+        self
+    }
+
+    /// Set the `Border` preset
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Add a `Style` for the border
+    pub fn border_style(mut self, style: Style) -> Self {
+        self.border_style = style;
+        self
+    }
+
+    /// Add a `Style` for the header row
+    ///
+    /// Styles of the cells are applied on top
+    pub fn header_style(mut self, style: Style) -> Self {
+        self.header_style = style;
+        self
+    }
+
+    /// Add styles for rows, repeating in order, such as for zebra stripes
+    ///
+    /// Styles of the cells are applied on top
+    pub fn row_styles(mut self, styles: impl IntoIterator<Item = Style>) -> Self {
+        self.row_styles = styles.into_iter().collect();
+        self
+    }
+
+    /// Shrink the widest columns to fit a width, such as of the terminal
+    ///
+    /// Cut text ends with `…`
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Format the table, with lines separated by newlines
+    ///
+    /// Empty if there are no cells, in the header or any row
+    pub fn render(&self) -> StyledString {
+        let rows: Vec<_> = self.header.iter().chain(&self.rows).collect();
+        let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let widths = self.column_widths(&rows, columns);
+        let chars = self.border.chars();
+
+        let mut lines = Vec::new();
+        if let Some(chars) = chars {
+            lines.push(self.rule(&widths, chars.top_left, chars.top, chars.top_right, chars));
+        }
+        if let Some(header) = &self.header {
+            lines.push(self.cells(header, &widths, self.header_style, chars));
+            if let Some(chars) = chars {
+                lines.push(self.rule(&widths, chars.left, chars.cross, chars.right, chars));
+            }
+        }
+        for (i, row) in self.rows.iter().enumerate() {
+            let style = match self.row_styles.len() {
+                0 => Style::new(),
+                len => self.row_styles[i % len],
+            };
+            lines.push(self.cells(row, &widths, style, chars));
+        }
+        if let Some(chars) = chars {
+            lines.push(self.rule(
+                &widths,
+                chars.bottom_left,
+                chars.bottom,
+                chars.bottom_right,
+                chars,
+            ));
+        }
+
+        let mut output = StyledString::new();
+        if columns == 0 {
+            return output;
+        }
+        for (i, line) in lines.into_iter().enumerate() {
+            if i > 0 {
+                output.push_plain("\n");
+            }
+            output.append(line);
+        }
+        output
+    }
+
+    /// Get the visible width of each column, shrunk to the maximum width
+    fn column_widths(&self, rows: &[&Vec<StyledString>], columns: usize) -> Vec<usize> {
+        let mut widths = vec![0; columns];
+        for row in rows {
+            for (width, cell) in widths.iter_mut().zip(*row) {
+                *width = (*width).max(cell.width());
+            }
+        }
+
+        if let Some(max_width) = self.max_width {
+            // Padding and borders
+            let extra = match self.border {
+                Border::None => 2 * columns.saturating_sub(1),
+                _ => 3 * columns + 1,
+            };
+            let max_width = max_width.saturating_sub(extra);
+
+            while widths.iter().sum::<usize>() > max_width {
+                match widths.iter_mut().max() {
+                    Some(width) if *width > 1 => *width -= 1,
+                    _ => break,
+                }
+            }
+        }
+
+        widths
+    }
+
+    /// Format a horizontal line of the border
+    fn rule(
+        &self,
+        widths: &[usize],
+        left: char,
+        cross: char,
+        right: char,
+        chars: BorderChars,
+    ) -> StyledString {
+        let mut line = String::from(left);
+        for (i, width) in widths.iter().enumerate() {
+            if i > 0 {
+                line.push(cross);
+            }
+            line += &chars.horizontal.to_string().repeat(width + 2);
+        }
+        line.push(right);
+
+        let mut output = StyledString::new();
+        output.push(line, self.border_style);
+        output
+    }
+
+    /// Format a row of cells, aligned and padded to the column widths
+    fn cells(
+        &self,
+        row: &[StyledString],
+        widths: &[usize],
+        style: Style,
+        chars: Option<BorderChars>,
+    ) -> StyledString {
+        let mut output = StyledString::new();
+        let empty = StyledString::new();
+
+        if let Some(chars) = chars {
+            output.push(chars.vertical.to_string(), self.border_style);
+            output.push(" ", style);
+        }
+        for (i, &width) in widths.iter().enumerate() {
+            if i > 0 {
+                match chars {
+                    Some(chars) => {
+                        output.push(" ", style);
+                        output.push(chars.vertical.to_string(), self.border_style);
+                        output.push(" ", style);
+                    }
+                    None => output.push("  ", style),
+                }
+            }
+
+            let cell = truncate(row.get(i).unwrap_or(&empty), width);
+            let space = width - cell.width();
+            let before = match self.alignments.get(i).copied().unwrap_or_default() {
+                Alignment::Left => 0,
+                Alignment::Center => space / 2,
+                Alignment::Right => space,
+            };
+
+            output.push(" ".repeat(before), style);
+            output.append_under(&cell, style);
+            output.push(" ".repeat(space - before), style);
+        }
+        if let Some(chars) = chars {
+            output.push(" ", style);
+            output.push(chars.vertical.to_string(), self.border_style);
+        }
+
+        output
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+/// Cut a cell to a visible width, ending with `…` if cut
+///
+/// A wide character which does not fit is removed, so the cell may be 1 column narrower
+///
+/// ANSI codes in the text are kept, with a reset if any were cut
+fn truncate(cell: &StyledString, width: usize) -> StyledString {
+    if cell.width() <= width {
+        return cell.clone();
+    }
+
+    let mut output = StyledString::new();
+    let mut remaining = width.saturating_sub(1);
+    let mut has_escape = false;
+
+    for span in cell.spans() {
+        let mut text = String::new();
+        let mut rest = span.text.as_str();
+        while let Some(ch) = rest.chars().next() {
+            let len = match escape_len(rest) {
+                0 => {
+                    let ch_width = ch.width().unwrap_or(0);
+                    if remaining == 0 || ch_width > remaining {
+                        break;
+                    }
+                    remaining -= ch_width;
+                    ch.len_utf8()
+                }
+                len => {
+                    has_escape = true;
+                    len
+                }
+            };
+            text += &rest[..len];
+            rest = &rest[len..];
+        }
+
+        let cut = !rest.is_empty();
+        if cut && width > 0 {
+            text.push('…');
+        }
+        if cut && has_escape {
+            text += "\x1b[0m";
+        }
+        output.push(text, span.style);
+        if cut {
+            break;
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{strip_ansi, style, stylize};
+
+    #[test]
+    fn table_works() {
+        let table = Table::new()
+            .header(["a", "bb"])
+            .row(["ccc", "d"])
+            .row(["e"])
+            .align(1, Alignment::Right)
+            .header_style(style!());
+
+        assert_eq!(
+            table.to_string(),
+            "┌─────┬────┐\n│ a   │ bb │\n├─────┼────┤\n│ ccc │  d │\n│ e   │    │\n└─────┴────┘"
+        );
+
+        assert_eq!(Table::new().to_string(), "");
+        assert_eq!(
+            Table::new()
+                .border(Border::None)
+                .row(["a", "b", "c"])
+                .align(1, Alignment::Center)
+                .row(["dd", "eee", "f"])
+                .to_string(),
+            "a    b   c\ndd  eee  f"
+        );
+        assert_eq!(
+            Table::new().border(Border::Ascii).row(["a"]).to_string(),
+            "+---+\n| a |\n+---+"
+        );
+    }
+
+    #[test]
+    fn table_works_w_styles() {
+        let table = Table::new()
+            .header(["a"])
+            .row(["b"])
+            .row([stylize!("c": Red)])
+            .row_styles([style!(), style!(+u)])
+            .border(Border::Heavy)
+            .border_style(style!(Blue));
+
+        assert_eq!(
+            table.to_string(),
+            "\x1b[34m┏━━━┓\x1b[0m\n\
+            \x1b[34m┃\x1b[0m\x1b[1m a \x1b[0m\x1b[34m┃\x1b[0m\n\
+            \x1b[34m┣━━━┫\x1b[0m\n\
+            \x1b[34m┃\x1b[0m b \x1b[34m┃\x1b[0m\n\
+            \x1b[34m┃\x1b[0m\x1b[4m \x1b[31mc\x1b[0m\x1b[4m \x1b[0m\x1b[34m┃\x1b[0m\n\
+            \x1b[34m┗━━━┛\x1b[0m"
+        );
+
+        // Styles of cells are applied on top of row styles
+        let mut cell = StyledString::new();
+        cell.push("x", style!(Red));
+        let table = Table::new()
+            .border(Border::None)
+            .row([cell])
+            .row_styles([style!(Green + b)]);
+        assert_eq!(table.to_string(), "\x1b[31;1mx\x1b[0m");
+    }
+
+    #[test]
+    fn table_works_w_max_width() {
+        let table = Table::new()
+            .row(["abcdef", "ab", stylize!("abcdef": Red).as_str()])
+            .max_width(19);
+
+        assert_eq!(
+            strip_ansi(&table.to_string()),
+            "┌──────┬────┬─────┐\n│ abc… │ ab │ ab… │\n└──────┴────┴─────┘"
+        );
+        assert!(table.to_string().contains("\x1b[31mab…\x1b[0m"));
+
+        let table = Table::new()
+            .border(Border::None)
+            .row(["abc", "de"])
+            .max_width(0);
+        assert_eq!(table.to_string(), "…  …");
+    }
+
+    #[test]
+    fn table_works_w_wide_chars() {
+        let table = Table::new().row(["日本", "a"]).row(["abc", "🦀"]);
+        assert_eq!(
+            table.to_string(),
+            "┌──────┬────┐\n│ 日本 │ a  │\n│ abc  │ 🦀 │\n└──────┴────┘"
+        );
+
+        let table = Table::new()
+            .border(Border::None)
+            .row(["日本語", "b"])
+            .max_width(7);
+        assert_eq!(table.to_string(), "日…   b");
+    }
+
+    #[test]
+    fn truncate_works() {
+        let mut cell = StyledString::new();
+        cell.push("ab", style!(Red));
+        cell.push("cd", style!());
+
+        assert_eq!(truncate(&cell, 4), cell);
+        assert_eq!(truncate(&cell, 3).to_string(), "\x1b[31mab\x1b[0m…");
+        assert_eq!(truncate(&cell, 2).to_string(), "\x1b[31ma…\x1b[0m");
+        assert_eq!(truncate(&cell, 0).to_string(), "");
+    }
+}
@@ -0,0 +1,569 @@
+use std::{collections::HashMap, ffi::OsString, io::IsTerminal};
+
+use unicode_width::UnicodeWidthStr;
+
+/// Standard output stream, for checking if color should be used
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stream {
+    /// Standard output
+    Stdout,
+    /// Standard error
+    Stderr,
+}
+
+impl Stream {
+    /// Check if the stream is a terminal
+    pub fn is_terminal(self) -> bool {
+        match self {
+            Stream::Stdout => std::io::stdout().is_terminal(),
+            Stream::Stderr => std::io::stderr().is_terminal(),
+        }
+    }
+
+    /// Check if color should be used for the stream
+    ///
+    /// Color is used if the stream is a terminal, unless `NO_COLOR` is set, or `TERM` is `dumb`.
+    /// Setting `CLICOLOR_FORCE` or `FORCE_COLOR` uses color, even if the stream is not a terminal,
+    /// and setting `FORCE_COLOR` to `0` or `false` uses no color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stilo::{stylize, strip_ansi, Stream};
+    /// let text = stylize!("error": Red);
+    ///
+    /// if Stream::Stderr.color_enabled() {
+    ///     eprintln!("{}", text);
+    /// } else {
+    ///     eprintln!("{}", strip_ansi(&text));
+    /// }
+    /// ```
+    pub fn color_enabled(self) -> bool {
+        color_enabled_with(
+            |name| std::env::var_os(name).map(|value| value.into_string().unwrap_or_default()),
+            self.is_terminal(),
+        )
+    }
+}
+
+/// Check if color should be used, with environment variables and if the stream is a terminal
+fn color_enabled_with(env: impl Fn(&str) -> Option<String>, is_terminal: bool) -> bool {
+    let is_set = |name| env(name).is_some_and(|value| !value.is_empty());
+
+    if is_set("NO_COLOR") {
+        return false;
+    }
+    if let Some(support) = forced_color(
+        env("FORCE_COLOR").as_deref(),
+        env("CLICOLOR_FORCE").as_deref(),
+    ) {
+        return support != ColorSupport::None;
+    }
+    if env("TERM").as_deref() == Some("dumb") {
+        return false;
+    }
+    is_terminal
+}
+
+/// Get the least colors forced with `FORCE_COLOR` or `CLICOLOR_FORCE`, from their values
+///
+/// Returns `Some(ColorSupport::None)` if color is disabled with `FORCE_COLOR`, and `None` if color
+/// is not forced
+fn forced_color(force_color: Option<&str>, clicolor_force: Option<&str>) -> Option<ColorSupport> {
+    match force_color {
+        Some("0" | "false") => return Some(ColorSupport::None),
+        Some("2") => return Some(ColorSupport::Ansi256),
+        Some("3") => return Some(ColorSupport::TrueColor),
+        Some(_) => return Some(ColorSupport::Ansi16),
+        None => (),
+    }
+    match clicolor_force {
+        Some("" | "0") | None => None,
+        Some(_) => Some(ColorSupport::Ansi16),
+    }
+}
+
+/// Collect environment variables into a map, skipping names or values which are not Unicode
+fn env_map(vars: impl IntoIterator<Item = (OsString, OsString)>) -> HashMap<String, String> {
+    vars.into_iter()
+        .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+        .collect()
+}
+
+/// Colors supported by a terminal
+///
+/// Ordered from least to most colors, so support can be compared
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{ColorSupport, Stream};
+/// let support = ColorSupport::detect(Stream::Stdout);
+///
+/// if support >= ColorSupport::TrueColor {
+///     println!("24-bit colors are supported");
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorSupport {
+    /// No color
+    None,
+    /// 16 basic colors
+    Ansi16,
+    /// 256-color palette, with `Color::Fixed`
+    Ansi256,
+    /// 24-bit color, with `Color::Rgb`
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// Detect the colors supported by a stream
+    ///
+    /// Returns `None` if the stream is not a terminal, unless color is forced with `FORCE_COLOR` or
+    /// `CLICOLOR_FORCE`. See `from_env_map` for how environment variables are used
+    pub fn detect(stream: Stream) -> Self {
+        if !stream.color_enabled() {
+            return Self::None;
+        }
+        match Self::from_env() {
+            // Terminal with unknown support
+            Self::None => Self::Ansi16,
+            support => support,
+        }
+    }
+
+    /// Detect supported colors from the environment variables of the process
+    ///
+    /// Does not check if a stream is a terminal
+    pub fn from_env() -> Self {
+        Self::from_env_map(&env_map(std::env::vars_os()))
+    }
+
+    /// Detect supported colors from a map of environment variables
+    ///
+    /// Checks, in order:
+    ///
+    /// - `NO_COLOR`: No color, if not empty
+    /// - `FORCE_COLOR`: `0` or `false` for no color. Otherwise, at least 16 colors, or 256 colors
+    ///   if `2`, or 24-bit color if `3`
+    /// - `CLICOLOR_FORCE`: At least 16 colors, if not empty or `0`
+    /// - `TERM`: No color if `dumb`, unless forced
+    /// - `COLORTERM`: 24-bit color if `truecolor` or `24bit`
+    /// - `WT_SESSION` and `TERM_PROGRAM`: Known terminals
+    /// - `CI`: Known CI providers, such as GitHub Actions and GitLab CI
+    /// - `TERM`: Names such as `xterm-256color`, `xterm-direct`, and `screen`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stilo::ColorSupport;
+    /// # use std::collections::HashMap;
+    /// let env = HashMap::from([("TERM".to_string(), "xterm-256color".to_string())]);
+    /// assert_eq!(ColorSupport::from_env_map(&env), ColorSupport::Ansi256);
+    /// ```
+    pub fn from_env_map(env: &HashMap<String, String>) -> Self {
+        let var = |name: &str| env.get(name).map(String::as_str);
+
+        if var("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+            return Self::None;
+        }
+
+        match forced_color(var("FORCE_COLOR"), var("CLICOLOR_FORCE")) {
+            Some(Self::None) => Self::None,
+            Some(forced) => forced.max(Self::from_terminal(var)),
+            None => Self::from_terminal(var),
+        }
+    }
+
+    /// Detect supported colors from the terminal, CI provider, and `TERM`
+    fn from_terminal<'a>(var: impl Fn(&str) -> Option<&'a str>) -> Self {
+        let term = var("TERM").unwrap_or_default();
+        if term == "dumb" {
+            return Self::None;
+        }
+
+        if matches!(var("COLORTERM"), Some("truecolor" | "24bit")) {
+            return Self::TrueColor;
+        }
+
+        // Windows Terminal
+        if var("WT_SESSION").is_some() {
+            return Self::TrueColor;
+        }
+        match var("TERM_PROGRAM") {
+            Some("iTerm.app" | "vscode" | "WezTerm" | "Hyper" | "ghostty") => {
+                return Self::TrueColor
+            }
+            Some("Apple_Terminal") => return Self::Ansi256,
+            _ => (),
+        }
+
+        if var("CI").is_some() {
+            if var("GITHUB_ACTIONS").is_some() || var("GITEA_ACTIONS").is_some() {
+                return Self::TrueColor;
+            }
+            const PROVIDERS: [&str; 7] = [
+                "GITLAB_CI",
+                "TRAVIS",
+                "CIRCLECI",
+                "APPVEYOR",
+                "BUILDKITE",
+                "DRONE",
+                "TEAMCITY_VERSION",
+            ];
+            if PROVIDERS.iter().any(|name| var(name).is_some()) {
+                return Self::Ansi16;
+            }
+            if var("CI_NAME") == Some("codeship") {
+                return Self::Ansi16;
+            }
+            return Self::None;
+        }
+
+        if term.ends_with("-direct") || term.contains("truecolor") || term.contains("24bit") {
+            return Self::TrueColor;
+        }
+        if term == "xterm-kitty" || term == "alacritty" || term == "xterm-ghostty" {
+            return Self::TrueColor;
+        }
+        if term.contains("256") {
+            return Self::Ansi256;
+        }
+
+        const BASIC: [&str; 11] = [
+            "xterm", "screen", "tmux", "vt100", "vt220", "rxvt", "color", "ansi", "cygwin",
+            "linux", "konsole",
+        ];
+        if BASIC.iter().any(|prefix| term.starts_with(prefix)) {
+            return Self::Ansi16;
+        }
+
+        Self::None
+    }
+}
+
+/// Remove ANSI escape codes from text
+///
+/// Removes style codes, hyperlinks, and other escape sequences.
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{stylize, strip_ansi};
+/// let text = stylize!("Hello": Red + bold);
+/// assert_eq!(strip_ansi(&text), "Hello");
+/// ```
+pub fn strip_ansi(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('\x1b') {
+        output += &rest[..start];
+        rest = &rest[start..];
+        rest = &rest[escape_len(rest)..];
+    }
+
+    output + rest
+}
+
+/// Update the escape codes which apply the current style, after an escape sequence in text
+///
+/// Only style codes change the state. Returns `true` for a style code
+pub(crate) fn update_style_state(state: &mut String, escape: &str) -> bool {
+    let Some(params) = escape
+        .strip_prefix("\x1b[")
+        .and_then(|escape| escape.strip_suffix('m'))
+    else {
+        return false;
+    };
+
+    // Reset, such as `ESC[0m`, `ESC[m`, or `ESC[0;31m`
+    if matches!(params.split(';').next(), Some("0" | "")) {
+        state.clear();
+        if !params.contains(';') {
+            return true;
+        }
+    }
+    state.push_str(escape);
+    true
+}
+
+/// Apply a style again after each reset in text, with the escape code which starts the style
+///
+/// Resets with other parameters, such as `ESC[0;31m`, are split, so the other parameters are
+/// applied on top of the style
+pub(crate) fn reapply_after_resets(text: &str, prefix: &str) -> String {
+    if prefix.is_empty() {
+        return text.into();
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('\x1b') {
+        let len = escape_len(&rest[start..]);
+        let escape = &rest[start..start + len];
+        output += &rest[..start];
+        rest = &rest[start + len..];
+
+        let params = escape
+            .strip_prefix("\x1b[")
+            .and_then(|escape| escape.strip_suffix('m'));
+        match params.map(|params| params.split_once(';').unwrap_or((params, ""))) {
+            Some(("0" | "", others)) => {
+                output += "\x1b[0m";
+                output += prefix;
+                if !others.is_empty() {
+                    output += &format!("\x1b[{others}m");
+                }
+            }
+            _ => output += escape,
+        }
+    }
+
+    output + rest
+}
+
+/// Get the number of terminal columns of text, without ANSI escape codes
+///
+/// Wide characters, such as CJK and emoji, take 2 columns, and zero-width characters take none
+pub(crate) fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut rest = text;
+
+    while let Some(start) = rest.find('\x1b') {
+        width += rest[..start].width();
+        rest = &rest[start..];
+        rest = &rest[escape_len(rest)..];
+    }
+
+    width + rest.width()
+}
+
+/// Get the length in bytes of the escape sequence at the start of text
+///
+/// Returns `0` if text does not start with `ESC`
+pub(crate) fn escape_len(text: &str) -> usize {
+    let Some(after) = text.strip_prefix('\x1b') else {
+        return 0;
+    };
+
+    let body_len = match after.chars().next() {
+        // Control sequence, such as style codes, ending with a byte in `@`..=`~`
+        Some('[') => after[1..]
+            .find(|ch| ('@'..='~').contains(&ch))
+            .map_or(after.len(), |end| end + 2),
+        // Operating system command, such as hyperlinks, ending with `BEL` or `ESC \`
+        Some(']') => match (after.find('\x07'), after.find("\x1b\\")) {
+            (Some(bel), Some(st)) if st < bel => st + 2,
+            (Some(bel), _) => bel + 1,
+            (None, Some(st)) => st + 2,
+            (None, None) => after.len(),
+        },
+        // Other escape sequence of two characters
+        Some(ch) => ch.len_utf8(),
+        None => 0,
+    };
+    1 + body_len
+}
+
+/// Don't use this
+#[doc(hidden)]
+pub fn text_for_stream(stream: Stream, text: String) -> String {
+    if stream.color_enabled() {
+        text
+    } else {
+        strip_ansi(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{stylize, Link};
+
+    #[test]
+    fn color_enabled_works() {
+        let enabled = |vars: &[(&str, &str)], is_terminal| {
+            color_enabled_with(
+                |name| {
+                    vars.iter()
+                        .find(|(key, _)| *key == name)
+                        .map(|(_, value)| value.to_string())
+                },
+                is_terminal,
+            )
+        };
+
+        assert!(enabled(&[], true));
+        assert!(!enabled(&[], false));
+        assert!(!enabled(&[("NO_COLOR", "1")], true));
+        assert!(enabled(&[("NO_COLOR", "")], true));
+        assert!(!enabled(&[("TERM", "dumb")], true));
+        assert!(enabled(&[("TERM", "xterm-256color")], true));
+        assert!(enabled(&[("CLICOLOR_FORCE", "1")], false));
+        assert!(enabled(&[("FORCE_COLOR", "true"), ("TERM", "dumb")], false));
+        assert!(!enabled(&[("FORCE_COLOR", "0")], false));
+        assert!(!enabled(&[("FORCE_COLOR", "false")], false));
+        assert!(!enabled(&[("FORCE_COLOR", "0")], true));
+        assert!(enabled(&[("FORCE_COLOR", "")], false));
+        assert!(!enabled(&[("CLICOLOR_FORCE", "0")], false));
+        assert!(!enabled(
+            &[("FORCE_COLOR", "false"), ("CLICOLOR_FORCE", "1")],
+            true
+        ));
+        assert!(!enabled(
+            &[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")],
+            true
+        ));
+    }
+
+    /// Detect color support from a list of environment variables
+    fn support(vars: &[(&str, &str)]) -> ColorSupport {
+        let env = vars
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        ColorSupport::from_env_map(&env)
+    }
+
+    #[test]
+    fn color_support_works() {
+        assert_eq!(support(&[]), ColorSupport::None);
+        assert_eq!(support(&[("TERM", "dumb")]), ColorSupport::None);
+        assert_eq!(support(&[("TERM", "xterm")]), ColorSupport::Ansi16);
+        assert_eq!(support(&[("TERM", "screen")]), ColorSupport::Ansi16);
+        assert_eq!(support(&[("TERM", "linux")]), ColorSupport::Ansi16);
+        assert_eq!(
+            support(&[("TERM", "xterm-256color")]),
+            ColorSupport::Ansi256
+        );
+        assert_eq!(support(&[("TERM", "tmux-256color")]), ColorSupport::Ansi256);
+        assert_eq!(
+            support(&[("TERM", "xterm-direct")]),
+            ColorSupport::TrueColor
+        );
+        assert_eq!(support(&[("TERM", "xterm-kitty")]), ColorSupport::TrueColor);
+        assert_eq!(support(&[("TERM", "unknown")]), ColorSupport::None);
+
+        assert_eq!(
+            support(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]),
+            ColorSupport::TrueColor
+        );
+        assert_eq!(
+            support(&[("TERM", "xterm"), ("COLORTERM", "24bit")]),
+            ColorSupport::TrueColor
+        );
+        assert_eq!(
+            support(&[
+                ("TERM", "xterm-256color"),
+                ("TERM_PROGRAM", "Apple_Terminal")
+            ]),
+            ColorSupport::Ansi256
+        );
+        assert_eq!(
+            support(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "iTerm.app")]),
+            ColorSupport::TrueColor
+        );
+        assert_eq!(support(&[("WT_SESSION", "abc")]), ColorSupport::TrueColor);
+    }
+
+    #[test]
+    fn color_support_works_w_overrides() {
+        assert_eq!(
+            support(&[("TERM", "xterm-direct"), ("NO_COLOR", "1")]),
+            ColorSupport::None
+        );
+        assert_eq!(
+            support(&[("TERM", "xterm-direct"), ("NO_COLOR", "")]),
+            ColorSupport::TrueColor
+        );
+        assert_eq!(support(&[("FORCE_COLOR", "1")]), ColorSupport::Ansi16);
+        assert_eq!(support(&[("FORCE_COLOR", "")]), ColorSupport::Ansi16);
+        assert_eq!(support(&[("FORCE_COLOR", "2")]), ColorSupport::Ansi256);
+        assert_eq!(
+            support(&[("FORCE_COLOR", "3"), ("TERM", "dumb")]),
+            ColorSupport::TrueColor
+        );
+        assert_eq!(
+            support(&[("FORCE_COLOR", "0"), ("COLORTERM", "truecolor")]),
+            ColorSupport::None
+        );
+        assert_eq!(
+            support(&[("FORCE_COLOR", "1"), ("COLORTERM", "truecolor")]),
+            ColorSupport::TrueColor
+        );
+        assert_eq!(support(&[("CLICOLOR_FORCE", "1")]), ColorSupport::Ansi16);
+        assert_eq!(
+            support(&[("CLICOLOR_FORCE", "1"), ("TERM", "xterm-256color")]),
+            ColorSupport::Ansi256
+        );
+        assert_eq!(support(&[("CLICOLOR_FORCE", "0")]), ColorSupport::None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn env_map_skips_non_unicode() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let env = env_map([
+            (OsString::from("TERM"), OsString::from("xterm-256color")),
+            (OsString::from("COLORTERM"), OsString::from_vec(vec![0xff])),
+            (OsString::from_vec(vec![0xfe]), OsString::from("1")),
+        ]);
+        assert_eq!(
+            env,
+            HashMap::from([("TERM".to_string(), "xterm-256color".to_string())])
+        );
+        assert_eq!(ColorSupport::from_env_map(&env), ColorSupport::Ansi256);
+    }
+
+    #[test]
+    fn color_support_works_in_ci() {
+        assert_eq!(
+            support(&[("CI", "true"), ("GITHUB_ACTIONS", "true")]),
+            ColorSupport::TrueColor
+        );
+        assert_eq!(
+            support(&[("CI", "true"), ("GITLAB_CI", "true")]),
+            ColorSupport::Ansi16
+        );
+        assert_eq!(
+            support(&[("CI", "true"), ("CI_NAME", "codeship")]),
+            ColorSupport::Ansi16
+        );
+        assert_eq!(
+            support(&[("CI", "true"), ("TERM", "xterm-256color")]),
+            ColorSupport::None
+        );
+        assert!(ColorSupport::TrueColor > ColorSupport::Ansi256);
+        assert!(ColorSupport::Ansi16 > ColorSupport::None);
+    }
+
+    #[test]
+    fn strip_ansi_works() {
+        assert_eq!(strip_ansi("Hello"), "Hello");
+        assert_eq!(strip_ansi(&stylize!("Héllo": Red + b)), "Héllo");
+        assert_eq!(strip_ansi("\x1b[38;5;208ma\x1b[0m b \x1b[1;4mc"), "a b c");
+        assert_eq!(strip_ansi(&Link::new("https://x.y").format("link")), "link");
+        assert_eq!(strip_ansi("\x1b]0;title\x07text\x1b7"), "text");
+        assert_eq!(strip_ansi("a\x1b[31"), "a");
+        assert_eq!(strip_ansi("a\x1b"), "a");
+        assert_eq!(strip_ansi("a\x1bé b"), "a b");
+        assert_eq!(visible_width(&stylize!("Héllo": Red + b)), 5);
+        assert_eq!(visible_width(&stylize!("日本": Red)), 4);
+        assert_eq!(visible_width("e\u{301}"), 1);
+    }
+
+    #[test]
+    fn reapply_after_resets_works() {
+        assert_eq!(reapply_after_resets("\x1b[31ma\x1b[0mb", ""), "\x1b[31ma\x1b[0mb");
+        assert_eq!(
+            reapply_after_resets("\x1b[31ma\x1b[0mb\x1b[mc", "\x1b[4m"),
+            "\x1b[31ma\x1b[0m\x1b[4mb\x1b[0m\x1b[4mc"
+        );
+        assert_eq!(
+            reapply_after_resets("a\x1b[0;32mb\x1b]8;;x\x07", "\x1b[1m"),
+            "a\x1b[0m\x1b[1m\x1b[32mb\x1b]8;;x\x07"
+        );
+    }
+}
@@ -61,3 +61,43 @@ fn builder_works() {
         }
     );
 }
+
+#[test]
+fn merge_works() {
+    let style = Style::new().color(Red).bold();
+
+    assert_eq!(style.merge(Style::new()), style);
+    assert_eq!(Style::new().merge(style), style);
+    assert_eq!(
+        style.merge(Style::new().color(Blue).italic()),
+        Style {
+            color: Some(Blue),
+            bold: true,
+            italic: true,
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        style.merge(Style::new().background(Green)),
+        Style {
+            color: Some(Red),
+            background: Some(Green),
+            bold: true,
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn prefix_and_suffix_work() {
+    let style = Style::new().color(Red).bold();
+    assert_eq!(style.prefix().to_string(), "\x1b[31;1m");
+    assert_eq!(style.suffix(), "\x1b[0m");
+    assert_eq!(
+        format!("{}hello{}", style.prefix(), style.suffix()),
+        style.format("hello")
+    );
+
+    assert_eq!(Style::new().prefix().to_string(), "");
+    assert_eq!(Style::new().suffix(), "");
+}
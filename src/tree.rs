@@ -0,0 +1,259 @@
+use std::fmt;
+
+use crate::{Style, StyledString};
+
+/// Node of a tree, with a styled label and child nodes
+///
+/// Labels can be a `StyledString`, or text with ANSI codes, such as from `stylize!`.
+/// Labels with newlines are drawn over many lines.
+///
+/// Create with `Tree::new(label)`, and format with a `TreeFormatter`
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{style, stylize, Tree};
+/// let tree = Tree::new(stylize!("stilo": Green + b))
+///     .child(Tree::new("serde").child(Tree::new("serde_derive")))
+///     .child(Tree::new("stilo-macros").style(style!(Blue)));
+///
+/// println!("{}", tree);
+/// assert_eq!(
+///     stilo::strip_ansi(&tree.to_string()),
+///     "\
+/// stilo
+/// ├── serde
+/// │   └── serde_derive
+/// └── stilo-macros"
+/// );
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Tree {
+    label: StyledString,
+    style: Style,
+    children: Vec<Tree>,
+}
+
+impl Tree {
+    /// Create a `Tree` node with a label, and no children
+    pub fn new(label: impl Into<StyledString>) -> Self {
+        Self {
+            label: label.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a `Style` for the label
+    ///
+    /// Styles of the label text are applied on top
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Add a child node
+    pub fn child(mut self, child: Tree) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Add a child node, without taking ownership
+    pub fn push(&mut self, child: Tree) {
+        self.children.push(child);
+    }
+
+    /// Get the child nodes
+    pub fn children(&self) -> &[Tree] {
+        &self.children
+    }
+
+    /// Get the number of nodes below this one
+    fn descendants(&self) -> usize {
+        self.children
+            .iter()
+            .map(|child| 1 + child.descendants())
+            .sum()
+    }
+}
+
+impl fmt::Display for Tree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", TreeFormatter::default().format(self))
+    }
+}
+
+/// Format a `Tree` with guide lines, such as `├──` and `└──`
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::{style, Tree, TreeFormatter};
+/// let tree = Tree::new("src")
+///     .child(Tree::new("lib.rs"))
+///     .child(Tree::new("highlight").child(Tree::new("mod.rs")).child(Tree::new("rust.rs")));
+///
+/// let formatter = TreeFormatter {
+///     guide: style!(+dim),
+///     max_depth: Some(1),
+///     ..Default::default()
+/// };
+///
+/// let text = formatter.format(&tree);
+/// println!("{}", text);
+/// assert_eq!(text.plain(), "src\n├── lib.rs\n└── highlight (+2)");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeFormatter {
+    /// Guide lines
+    pub guide: Style,
+    /// Deepest level of nodes to show, where the root is `0`
+    ///
+    /// Nodes with hidden children end with the number of hidden nodes, such as `(+2)`
+    pub max_depth: Option<usize>,
+    /// Number of hidden nodes, after collapsed nodes
+    pub collapsed: Style,
+}
+
+impl Default for TreeFormatter {
+    fn default() -> Self {
+        Self {
+            guide: Style::new(),
+            max_depth: None,
+            collapsed: Style::new().dim(),
+        }
+    }
+}
+
+impl TreeFormatter {
+    /// Create a `TreeFormatter` with no maximum depth and the default styles
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Format a tree, with lines separated by newlines
+    pub fn format(&self, tree: &Tree) -> StyledString {
+        let mut output = StyledString::new();
+        self.write_node(&mut output, tree, &mut String::new(), None, 0);
+        output
+    }
+
+    /// Write a node and its children
+    ///
+    /// `prefix` is the guide lines of parent nodes, and `last` is if this is the last child,
+    /// or `None` for the root
+    fn write_node(
+        &self,
+        output: &mut StyledString,
+        tree: &Tree,
+        prefix: &mut String,
+        last: Option<bool>,
+        depth: usize,
+    ) {
+        let (branch, indent) = match last {
+            None => ("", ""),
+            Some(false) => ("├── ", "│   "),
+            Some(true) => ("└── ", "    "),
+        };
+
+        for (i, line) in tree.label.lines().into_iter().enumerate() {
+            if depth > 0 || i > 0 {
+                output.push_plain("\n");
+            }
+            output.push(prefix.as_str(), self.guide);
+            output.push(if i == 0 { branch } else { indent }, self.guide);
+            output.append_under(&line, tree.style);
+        }
+
+        if self.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+            if !tree.children.is_empty() {
+                output.push_plain(" ");
+                output.push(format!("(+{})", tree.descendants()), self.collapsed);
+            }
+            return;
+        }
+
+        let len = prefix.len();
+        prefix.push_str(indent);
+        for (i, child) in tree.children.iter().enumerate() {
+            let last = i + 1 == tree.children.len();
+            self.write_node(output, child, prefix, Some(last), depth + 1);
+        }
+        prefix.truncate(len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{style, stylize};
+
+    fn tree() -> Tree {
+        Tree::new("a")
+            .child(
+                Tree::new("b")
+                    .child(Tree::new("c").child(Tree::new("d")))
+                    .child(Tree::new("e")),
+            )
+            .child(Tree::new("f").child(Tree::new("g")))
+    }
+
+    #[test]
+    fn tree_works() {
+        assert_eq!(
+            tree().to_string(),
+            "a\n├── b\n│   ├── c\n│   │   └── d\n│   └── e\n└── f\n    └── g"
+        );
+        assert_eq!(Tree::new("a").to_string(), "a");
+        assert_eq!(Tree::default().to_string(), "");
+
+        let mut tree = Tree::new("a\nb");
+        tree.push(Tree::new("c\nd").child(Tree::new("e")));
+        tree.push(Tree::new("f\ng"));
+        assert_eq!(
+            tree.to_string(),
+            "a\nb\n├── c\n│   d\n│   └── e\n└── f\n    g"
+        );
+        assert_eq!(tree.children().len(), 2);
+    }
+
+    #[test]
+    fn tree_works_w_max_depth() {
+        let formatter = TreeFormatter {
+            max_depth: Some(1),
+            collapsed: style!(),
+            ..Default::default()
+        };
+        assert_eq!(
+            formatter.format(&tree()).to_string(),
+            "a\n├── b (+3)\n└── f (+1)"
+        );
+
+        let formatter = TreeFormatter {
+            max_depth: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(
+            formatter.format(&tree()).to_string(),
+            "a \x1b[2m(+6)\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn tree_works_w_styles() {
+        let tree = Tree::new(stylize!("a": Red))
+            .style(style!(+b))
+            .child(Tree::new("b").style(style!(Blue)))
+            .child(Tree::new("c"));
+        let formatter = TreeFormatter {
+            guide: style!(+dim),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            formatter.format(&tree).to_string(),
+            "\x1b[1m\x1b[31ma\x1b[0m\x1b[1m\x1b[0m\n\
+            \x1b[2m├── \x1b[0m\x1b[34mb\x1b[0m\n\
+            \x1b[2m└── \x1b[0mc"
+        );
+    }
+}
@@ -18,28 +18,309 @@ macro_rules! println_styles {
     };
 }
 
-/// Format stylized strings individually, concatenated.
+/// Format stylized strings individually, concatenated, to stderr.
 ///
 /// Wrapper for `stylize_many!`
+///
+/// Styles are removed if color is not enabled for stderr. See `Stream::color_enabled`
 #[macro_export]
-macro_rules! write_styles {
-    ( $f: expr, $( $arg: tt )* ) => {
-        write!($f, "{}", $crate::stylize_many!($( $arg )*))
+macro_rules! eprint_styles {
+    ( $( $arg: tt )* ) => {
+        eprint!("{}", $crate::text_for_stream(
+            $crate::Stream::Stderr,
+            $crate::stylize_many!($( $arg )*),
+        ))
     };
 }
 
-/// Format stylized strings individually, concatenated.
+/// Format stylized strings individually, concatenated, to stderr.
 ///
 /// Wrapper for `stylize_many!`
+///
+/// Styles are removed if color is not enabled for stderr. See `Stream::color_enabled`
+#[macro_export]
+macro_rules! eprintln_styles {
+    ( $( $arg: tt )* ) => {
+        eprintln!("{}", $crate::text_for_stream(
+            $crate::Stream::Stderr,
+            $crate::stylize_many!($( $arg )*),
+        ))
+    };
+}
+
+/// Write stylized strings individually, concatenated.
+///
+/// Arguments are the same as `stylize_many!`
+///
+/// Each argument is written directly to the writer, without creating a `String`.
+/// Works with `io::Write` and `fmt::Write`, and returns the first error.
+///
+/// The writer expression is evaluated once, and borrowed the same way as with `write!`.
+///
+/// The writer must implement only one of `io::Write` and `fmt::Write`.
+/// For a type which implements both, write to it as `&mut dyn io::Write` or `&mut dyn fmt::Write`.
+///
+/// # Examples
+///
+/// ```
+/// # use stilo::write_styles;
+/// use std::io::Write;
+///
+/// let mut stdout = std::io::stdout();
+/// let world = "World!";
+///
+/// write_styles!(stdout, "Hello ": Red; "{}": Blue + b, world; "\n").unwrap();
+/// ```
+///
+/// Writers with both `io::Write` and `fmt::Write`:
+///
+/// ```
+/// # use stilo::write_styles;
+/// # struct Both(String);
+/// # impl std::io::Write for Both {
+/// #     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> { Ok(buf.len()) }
+/// #     fn flush(&mut self) -> std::io::Result<()> { Ok(()) }
+/// # }
+/// # impl std::fmt::Write for Both {
+/// #     fn write_str(&mut self, s: &str) -> std::fmt::Result { self.0 += s; Ok(()) }
+/// # }
+/// use std::fmt::Write;
+///
+/// let mut both = Both(String::new());
+/// write_styles!(&mut both as &mut dyn Write, "Hello": Red).unwrap();
+/// assert_eq!(both.0, "\x1b[31mHello\x1b[0m");
+/// ```
+///
+/// ```compile_fail
+/// # use stilo::write_styles;
+/// # struct Both(String);
+/// # impl std::io::Write for Both {
+/// #     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> { Ok(buf.len()) }
+/// #     fn flush(&mut self) -> std::io::Result<()> { Ok(()) }
+/// # }
+/// # impl std::fmt::Write for Both {
+/// #     fn write_str(&mut self, s: &str) -> std::fmt::Result { self.0 += s; Ok(()) }
+/// # }
+/// use std::fmt::Write;
+///
+/// // Ambiguous writer
+/// let mut both = Both(String::new());
+/// write_styles!(both, "Hello": Red).unwrap();
+/// ```
+#[macro_export]
+macro_rules! write_styles {
+    // Internal: write each argument, after splitting with `split_segments!`
+    // Stops at the first error
+    ( @segments $f: ident, $result: ident $( [ $( $segment: tt )+ ] )* ) => {
+        $(
+            if $result.is_ok() {
+                $result = $crate::write_styles!( @write $f, $( $segment )+ );
+            }
+        )*
+    };
+
+    // Internal: write text with a style
+    ( @styled $f: expr, $style: expr, $( $text: tt )* ) => {{
+        let style: $crate::Style = $style;
+        write!($f, "{}{}{}", style.prefix(), $( $text )*, style.suffix())
+    }};
+
+    // Internal: no style, just text
+    ( @write $f: expr, $text: literal $(, $arg: expr )* $(,)? ) => {
+        write!($f, $text, $( $arg, )*)
+    };
+
+    // Internal: conditional, with no style
+    ( @write $f: expr, $text: literal : $( if $condition: expr )? $(, $arg: expr )* $(,)? ) => {
+        compile_error!("Cannot use conditional style, if no styles are included")
+    };
+
+    // Internal: with existing style
+    (
+        @write $f: expr,
+        $text: literal :
+        $style: block
+        $( if $condition: expr )?
+        $(, $arg: expr )* $(,)?
+    ) => {
+        $crate::write_styles!(
+            @styled $f,
+            if $crate::fallback_metavar!( $({ $condition })? {true} ) {
+                $style
+            } else {
+                $crate::Style::new()
+            },
+            format_args!($text, $( $arg, )*)
+        )
+    };
+
+    // Internal: color and decoration
+    (
+        @write $f: expr,
+        $text: literal :
+        $( $color: ident )?
+        $( + $decor: ident )*
+        $( if $condition: expr )?
+        $(, $arg: expr )* $(,)?
+    ) => {
+        $crate::write_styles!(
+            @write $f,
+            $text : { $crate::style!( $( $color )? $( + $decor )* ) }
+            $( if $condition )?
+            $(, $arg )*
+        )
+    };
+
+    // Internal: style with `else`
+    ( @write $f: expr, $text: literal : $( $rest: tt )+ ) => {
+        $crate::write_styles!( @else $f, $text [] $( $rest )+ )
+    };
+
+    // Internal: end of style with `else`, with format arguments
+    (
+        @else $f: expr, $text: literal [ $( $style: tt )+ ]
+        $(, $arg: expr )* $(,)?
+    ) => {
+        $crate::write_styles!(
+            @styled $f,
+            $crate::style!( $( $style )+ ),
+            format_args!($text, $( $arg, )*)
+        )
+    };
+
+    // Internal: add token to style with `else`
+    ( @else $f: expr, $text: literal [ $( $style: tt )* ] $next: tt $( $rest: tt )* ) => {
+        $crate::write_styles!( @else $f, $text [ $( $style )* $next ] $( $rest )* )
+    };
+
+    // Internal: expression, conditional with no style
+    ( @write $f: expr, $text: expr => $( if $condition: expr )? $(,)? ) => {
+        compile_error!("Cannot use conditional style, if no styles are included")
+    };
+
+    // Internal: expression, with existing style
+    (
+        @write $f: expr,
+        $text: expr =>
+        $style: block
+        $( if $condition: expr )? $(,)?
+    ) => {
+        $crate::write_styles!(
+            @styled $f,
+            if $crate::fallback_metavar!( $({ $condition })? {true} ) {
+                $style
+            } else {
+                $crate::Style::new()
+            },
+            $text
+        )
+    };
+
+    // Internal: expression, color and decoration
+    (
+        @write $f: expr,
+        $text: expr =>
+        $( $color: ident )?
+        $( + $decor: ident )*
+        $( if $condition: expr )? $(,)?
+    ) => {
+        $crate::write_styles!(
+            @write $f,
+            $text => { $crate::style!( $( $color )? $( + $decor )* ) }
+            $( if $condition )?
+        )
+    };
+
+    // Internal: expression, style with `else`
+    ( @write $f: expr, $text: expr => $( $rest: tt )+ ) => {
+        $crate::write_styles!( @styled $f, $crate::style!( $( $rest )+ ), $text )
+    };
+
+    // No arguments
+    ( $f: expr $(,)? ) => {
+        write!($f, "")
+    };
+
+    ( $f: expr, $( $arg: tt )* ) => {{
+        #[allow(unused_imports)]
+        use $crate::{FmtWriterRef as _, IoWriterRef as _};
+        match $f.writer_ref() {
+            writer => {
+                // Gives `result` the error type of the writer, even with no arguments
+                #[allow(unused_mut)]
+                let mut result = write!(writer, "");
+                $crate::split_segments!(
+                    [$crate::write_styles] [@segments writer, result] $( $arg )*
+                );
+                result
+            }
+        }
+    }};
+}
+
+/// Write stylized strings individually, concatenated, with a newline.
+///
+/// Arguments are the same as `stylize_many!`
+///
+/// Each argument is written directly to the writer, without creating a `String`.
+/// Works with `io::Write` and `fmt::Write`, and returns the first error.
+///
+/// The writer must implement only one of `io::Write` and `fmt::Write`, like `write_styles!`
 #[macro_export]
 macro_rules! writeln_styles {
-    ( $f: expr, $( $arg: tt )* ) => {
-        writeln!($f, "{}", $crate::stylize_many!($( $arg )*))
+    // No arguments
+    ( $f: expr $(,)? ) => {
+        writeln!($f)
     };
+
+    ( $f: expr, $( $arg: tt )* ) => {{
+        #[allow(unused_imports)]
+        use $crate::{FmtWriterRef as _, IoWriterRef as _};
+        match $f.writer_ref() {
+            writer => {
+                let mut result = write!(writer, "");
+                $crate::split_segments!(
+                    [$crate::write_styles] [@segments writer, result] $( $arg )*
+                );
+                if result.is_ok() {
+                    result = writeln!(writer);
+                }
+                result
+            }
+        }
+    }};
+}
+
+/// Don't use this
+///
+/// Borrows an `io::Write` writer the same way as `write!`, so `write_styles!` can evaluate the
+/// writer expression once
+#[doc(hidden)]
+pub trait IoWriterRef: std::io::Write {
+    fn writer_ref(&mut self) -> &mut Self {
+        self
+    }
 }
 
+impl<W: std::io::Write + ?Sized> IoWriterRef for W {}
+
+/// Don't use this
+///
+/// Borrows a `fmt::Write` writer the same way as `write!`, so `write_styles!` can evaluate the
+/// writer expression once
+#[doc(hidden)]
+pub trait FmtWriterRef: std::fmt::Write {
+    fn writer_ref(&mut self) -> &mut Self {
+        self
+    }
+}
+
+impl<W: std::fmt::Write + ?Sized> FmtWriterRef for W {}
+
 #[cfg(test)]
 mod tests {
+    use crate::{style, stylize_many};
+
     #[test]
     fn println_styles_compiles() {
         let world = "World!";
@@ -61,4 +342,157 @@ mod tests {
             "\nHello {world} {}", 123;
         );
     }
+
+    #[test]
+    fn write_styles_works() {
+        use std::fmt::Write;
+
+        let world = "World!";
+        let style = style!(Yellow + i);
+
+        let mut text = String::new();
+        write_styles!(text, "Hello").unwrap();
+        write_styles!(
+            text,
+            " {}", world;
+            "\nHello": Red + b;
+            "\nHello {}": {style}, world;
+            "\nHello": {style} if false;
+            "\nHello": Green if false else Blue;
+            "\n" => {style};
+            world.len() => Red + u;
+            world => Green if false;
+        )
+        .unwrap();
+        writeln_styles!(&mut text, "\nHello {}": +i, world,).unwrap();
+
+        let expected = stylize_many!(
+            "Hello";
+            " {}", world;
+            "\nHello": Red + b;
+            "\nHello {}": {style}, world;
+            "\nHello": {style} if false;
+            "\nHello": Green if false else Blue;
+            "\n" => {style};
+            world.len() => Red + u;
+            world => Green if false;
+            "\nHello {}": +i, world;
+            "\n";
+        );
+        assert_eq!(text, expected);
+
+        // No arguments
+        let mut text = String::new();
+        write_styles!(text,).unwrap();
+        write_styles!(text, ;).unwrap();
+        writeln_styles!(text).unwrap();
+        writeln_styles!(&mut text,).unwrap();
+        assert_eq!(text, "\n\n");
+    }
+
+    #[test]
+    fn write_styles_works_w_io() {
+        use std::io::Write;
+
+        let mut bytes = Vec::new();
+        write_styles!(bytes, "Hello": Red; " World").unwrap();
+        writeln_styles!(&mut bytes, "!": +b).unwrap();
+        assert_eq!(bytes, stylize_many!("Hello": Red; " World"; "!": +b; "\n").as_bytes());
+
+        // Returns the first error, and stops writing
+        let mut buffer = [0u8; 8];
+        let mut slice = &mut buffer[..];
+        let error = write_styles!(slice, "Hello"; " World": Red; "!").unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+        assert_eq!(&buffer, b"Hello\x1b[3");
+
+        // Slices are advanced, like with `write!`
+        let mut buffer = [0u8; 4];
+        let mut slice = &mut buffer[..];
+        write_styles!(slice, "ab").unwrap();
+        write!(slice, "cd").unwrap();
+        assert_eq!(&buffer, b"abcd");
+
+        // Temporary writer
+        let mut bytes = Vec::new();
+        write_styles!(std::io::Cursor::new(&mut bytes), "a"; "b": Red).unwrap();
+        assert_eq!(bytes, stylize_many!("a"; "b": Red).as_bytes());
+    }
+
+    #[test]
+    fn write_styles_works_w_many_arguments() {
+        use std::fmt::Write;
+
+        let mut text = String::new();
+        // More arguments than the recursion limit, when split one token at a time
+        write_styles!(
+            text,
+            "a": Red + b; "b"; "a": Red + b; "b"; "a": Red + b; "b"; "a": Red + b; "b";
+            "a": Red + b; "b"; "a": Red + b; "b"; "a": Red + b; "b"; "a": Red + b; "b";
+            "a": Red + b; "b"; "a": Red + b; "b"; "a": Red + b; "b"; "a": Red + b; "b";
+            "a": Red + b; "b"; "a": Red + b; "b"; "a": Red + b; "b"; "a": Red + b; "b";
+        )
+        .unwrap();
+
+        assert_eq!(text, "\x1b[31;1ma\x1b[0mb".repeat(16));
+    }
+
+    #[test]
+    fn write_styles_evaluates_writer_once() {
+        use std::fmt::Write;
+
+        let mut texts = [String::new(), String::new()];
+        let mut calls = 0;
+
+        write_styles!(
+            texts[{
+                calls += 1;
+                0
+            }],
+            "a": Red; "b"; "c": +b
+        )
+        .unwrap();
+        writeln_styles!(
+            texts[{
+                calls += 1;
+                1
+            }],
+            "d"; "e"
+        )
+        .unwrap();
+
+        assert_eq!(calls, 2);
+        assert_eq!(texts[0], stylize_many!("a": Red; "b"; "c": +b));
+        assert_eq!(texts[1], "de\n");
+
+    }
+
+    #[test]
+    fn write_styles_works_in_display() {
+        struct Greeting(&'static str);
+
+        impl std::fmt::Display for Greeting {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write_styles!(f, "Hello ": Red; "{}": +b, self.0)
+            }
+        }
+
+        assert_eq!(
+            Greeting("World").to_string(),
+            stylize_many!("Hello ": Red; "World": +b)
+        );
+    }
+
+    #[test]
+    fn eprintln_styles_compiles() {
+        let world = "World!";
+
+        eprint_styles!("Hello": Red; " ");
+        eprintln_styles!(
+            "error": Red + b;
+            ": ";
+            "Hello {}": +i, world;
+            world => Green if false else Yellow;
+        );
+    }
 }